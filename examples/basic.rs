@@ -25,7 +25,7 @@ fn main() {
     table.add_row(Row::new(vec![Cell::new("foobar2"), Cell::new("bar2"), Cell::new("foo2")]));
     table.printstd();
     println!("Modified : ");
-    table.set_element("new_foo", 2, 1).unwrap();
+    table.set_cell(1, 2, "new_foo").unwrap();
     table.printstd();
 
     // The same table can be built the following way :
@@ -0,0 +1,53 @@
+use prettytable::Table;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/*
+    Compares the memory used by a large table with a low-cardinality
+    status column, with and without interned cells.
+*/
+
+struct Counter;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for Counter {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATED.fetch_add(layout.size(), Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        ALLOCATED.fetch_sub(layout.size(), Ordering::SeqCst);
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: Counter = Counter;
+
+const ROWS: usize = 100_000;
+
+fn status(i: usize) -> &'static str {
+    ["SUCCEEDED", "SUCCEEDED", "FAILED"][i % 3]
+}
+
+fn measure<F: FnOnce(&mut Table)>(fill: F) -> usize {
+    let before = ALLOCATED.load(Ordering::SeqCst);
+    let mut table = Table::new();
+    fill(&mut table);
+    let used = ALLOCATED.load(Ordering::SeqCst) - before;
+    drop(table);
+    used
+}
+
+fn main() {
+    let plain = measure(|t| for i in 0..ROWS {
+        t.add_row(vec![status(i), "production"].into());
+    });
+    let interned = measure(|t| for i in 0..ROWS {
+        t.add_row_interned(vec![status(i), "production"]);
+    });
+    println!("{} rows, plain cells:    {} bytes", ROWS, plain);
+    println!("{} rows, interned cells: {} bytes", ROWS, interned);
+}
@@ -0,0 +1,97 @@
+//! Build a table from arbitrary input, and check that rendering and exporting it never panics.
+//!
+//! Run with `cargo fuzz run render` from the root of the repository.
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use prettytable::format::{consts, Alignment, FitStrategy, NumberFormat, NumberLocale, VAlign};
+use prettytable::{Cell, Row, Slice, Table};
+
+/// Separates rows in the input
+const ROW_SEP: char = '\u{1e}';
+/// Separates cells in the input
+const CELL_SEP: char = '\u{1f}';
+
+fuzz_target!(|data: &[u8]| {
+    if data.len() < 4 {
+        return;
+    }
+    let (settings, text) = data.split_at(4);
+    let text = String::from_utf8_lossy(text);
+
+    let mut table = Table::new();
+    for (i, line) in text.split(ROW_SEP).enumerate() {
+        let cells = line
+            .split(CELL_SEP)
+            .enumerate()
+            .map(|(j, s)| {
+                let mut cell = Cell::new(s).with_hspan((i + j) % 3);
+                match (settings[0] as usize + j) % 5 {
+                    0 => cell.align(Alignment::RIGHT),
+                    1 => cell.align(Alignment::CENTER),
+                    2 => cell.set_anchor(".".to_string()),
+                    3 => cell.set_max_width(Some(settings[1] as usize % 8)),
+                    _ => {}
+                }
+                cell
+            })
+            .collect();
+        if i == 0 && settings[0] & 1 == 1 {
+            table.set_titles(Row::new(cells));
+        } else {
+            table.add_row(Row::new(cells));
+        }
+    }
+
+    let formats = [
+        *consts::FORMAT_DEFAULT,
+        *consts::FORMAT_CLEAN,
+        *consts::FORMAT_BOX_CHARS,
+        *consts::FORMAT_NO_COLSEP,
+        *consts::FORMAT_BORDERS_ONLY,
+    ];
+    table.set_format(formats[settings[1] as usize % formats.len()]);
+    let width = settings[2] as usize % 64;
+    for col in 0..4 {
+        match (settings[2] as usize >> col) % 10 {
+            0 => table.set_column_fixed(col, width % 5),
+            1 => table.set_column_empty_default(col, "-".to_string()),
+            2 => table.set_column_merge_vertical(col, true),
+            3 => table.set_column_valign(col, VAlign::Middle),
+            4 => table.set_column_valign(col, VAlign::Bottom),
+            5 => table.set_column_number_format(col, NumberFormat::new().compact(width % 4)),
+            6 => table.set_column_number_format(col, NumberFormat::new().locale(NumberLocale::french())),
+            7 => table.set_column_anchor(col, ".".to_string()),
+            _ => {}
+        }
+    }
+    match settings[3] % 4 {
+        0 => table.set_absolute_max_width(Some(width % 8)),
+        1 => table.fit_to_width(width, FitStrategy::Proportional),
+        2 => table.fit_to_width(width, FitStrategy::Balanced),
+        _ => {}
+    }
+    table.set_titles_visible(settings[3] & 4 == 0);
+
+    let _ = table.to_string();
+    let _ = format!("{:>1$}", table, width);
+    let mut out = Vec::new();
+    let _ = table.print(&mut out);
+    let _ = table.print_html(&mut out);
+    let _ = table.print_rotated(&mut out);
+    let _ = table.print_highlighted(&mut out, ".");
+    let _ = table.append_rows_to_writer(&mut out, 1, &[width % 3, width % 7]);
+    let _ = table.render_rows(&mut out, table.row_iter(), &[width % 5], true);
+    let _ = table.slice(1.min(table.len())..).to_string();
+    let _ = Table::print_many(&[&table, &table], &mut out);
+    let _ = table.to_csv(Vec::new());
+    let _ = table.to_csv_canonical(Vec::new());
+    let _ = table.to_clipboard_formats();
+    let _ = Table::from_auto_delimited(text.as_bytes());
+
+    let _ = table.set_cell_span(0, width % 3, width % 4);
+    let _ = table.add_total_footer(0, &[1, 2]);
+    table.drop_empty_columns();
+    let _ = table.insert_column(width % 4, "new", "value");
+    let _ = table.to_string();
+});
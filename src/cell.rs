@@ -1,11 +1,13 @@
 //! This module contains definition of table/row cells stuff
 
 use super::format::Alignment;
-use super::utils::{display_width, print_align, HtmlEscape};
+use super::utils::{anchor_offset, clip, display_width, escape_sequences, print_align, readable_color, truncate, wrap_text, Background, HtmlEscape};
 use super::{color, Attr, Terminal};
+use std::borrow::Cow;
 use std::io::{Error, Write};
 use std::string::ToString;
 use std::str::FromStr;
+use std::sync::Arc;
 
 /// Represent a table cell containing a string.
 ///
@@ -13,43 +15,91 @@ use std::str::FromStr;
 /// The cell would have to be replaced by another one
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub struct Cell {
-    content: Vec<String>,
+    content: Vec<Arc<str>>,
+    lines: Vec<Arc<str>>,
     width: usize,
-    align: Alignment,
+    max_width: Option<usize>,
+    align: Option<Alignment>,
     style: Vec<Attr>,
     hspan: usize,
+    precision: Option<usize>,
+    annotation: Option<String>,
+    anchor: Option<String>,
 }
 
 impl Cell {
     /// Create a new `Cell` initialized with content from `string`.
     /// Text alignment in cell is configurable with the `align` argument
     pub fn new_align(string: &str, align: Alignment) -> Cell {
-        let content: Vec<String> = string.lines().map(|x| x.to_string()).collect();
-        let mut width = 0;
-        for cont in &content {
-            let l = display_width(&cont[..]);
-            if l > width {
-                width = l;
-            }
-        }
-        Cell {
-            content: content,
-            width: width,
-            align: align,
-            style: Vec::new(),
-            hspan: 1,
-        }
+        let mut cell = Cell::new(string);
+        cell.align(align);
+        cell
+    }
+
+    /// Recompute the rendered lines and the width of the cell
+    fn update_lines(&mut self) {
+        self.lines = match self.max_width {
+            Some(w) => wrap_text(&self.get_content(), w).into_iter().map(Arc::from).collect(),
+            None => self.content.clone(),
+        };
+        self.width = self.lines.iter().map(|l| display_width(l)).max().unwrap_or(0);
     }
 
     /// Create a new `Cell` initialized with content from `string`.
-    /// By default, content is align to `LEFT`
+    /// By default, content is aligned like the column it belongs to, which is `LEFT` unless
+    /// configured otherwise in the table
     pub fn new(string: &str) -> Cell {
-        Cell::new_align(string, Alignment::LEFT)
+        Cell::with_content(string.lines().map(Arc::from).collect())
+    }
+
+    /// Create a new `Cell` sharing the storage of `string`.
+    /// This saves memory when many cells have the same content, see `Table::add_row_interned`.
+    /// Only single-line content is shared, multiline content is copied
+    pub fn new_shared(string: Arc<str>) -> Cell {
+        if string.is_empty() || string.contains(&['\n', '\r'][..]) {
+            return Cell::new(&string);
+        }
+        Cell::with_content(vec![string])
     }
 
-    /// Set text alignment in the cell
+    fn with_content(content: Vec<Arc<str>>) -> Cell {
+        let mut cell = Cell {
+            lines: Vec::new(),
+            content,
+            width: 0,
+            max_width: None,
+            align: None,
+            style: Vec::new(),
+            hspan: 1,
+            precision: None,
+            annotation: None,
+            anchor: None,
+        };
+        cell.update_lines();
+        cell
+    }
+
+    /// Set text alignment in the cell. It takes precedence over the column's alignment
     pub fn align(&mut self, align: Alignment) {
-        self.align = align;
+        self.align = Some(align);
+    }
+
+    /// Get the text alignment of the cell. Returns `LEFT` if no alignment has been set on the cell
+    pub fn get_alignment(&self) -> Alignment {
+        self.align.unwrap_or(Alignment::LEFT)
+    }
+
+    /// Align the cell on the first occurrence of `anchor`, so that it is at the same position as
+    /// in the other cells of the column anchored on a string. If the cell doesn't contain it,
+    /// the cell is aligned as if it was at its end. It takes precedence over any alignment, and
+    /// only applies to text rendering. See `Table::set_column_anchor`
+    pub fn set_anchor(&mut self, anchor: String) {
+        self.anchor = Some(anchor);
+    }
+
+    /// Get the string the cell is aligned on, if any
+    pub fn get_anchor(&self) -> Option<&str> {
+        self.anchor.as_ref().map(String::as_str)
     }
 
     /// Add a style attribute to the cell
@@ -69,10 +119,66 @@ impl Cell {
         self
     }
 
-    /// Remove all style attributes and reset alignment to default (LEFT)
+    /// Replace the content of the cell, keeping its style, alignment and span
+    pub (crate) fn set_content(&mut self, string: &str) {
+        self.content = string.lines().map(Arc::from).collect();
+        self.update_lines();
+    }
+
+    /// Return a copy of the cell whose lines wider than `width` are clipped according to
+    /// the alignment, `col_align` being the alignment used if none has been set on the cell.
+    /// Anchored cells are clipped like left aligned ones
+    pub (crate) fn clipped(&self, width: usize, col_align: Alignment) -> Cell {
+        let align = if self.anchor.is_some() { Alignment::LEFT } else { self.align.unwrap_or(col_align) };
+        let mut cell = self.clone();
+        cell.max_width = None;
+        cell.content = self.lines.iter().map(|l| Arc::from(clip(l, width, align))).collect();
+        cell.update_lines();
+        cell
+    }
+
+    /// Set the maximum width of the cell, or `None` to disable wrapping.
+    ///
+    /// Explicit newlines in the content are honored first, then each line longer
+    /// than `max_width` is wrapped on spaces, or on characters for words which don't fit.
+    /// A maximum width of 0 is treated as 1.
+    pub fn set_max_width(&mut self, max_width: Option<usize>) {
+        self.max_width = max_width.map(|w| if w == 0 {1} else {w});
+        self.update_lines();
+    }
+
+    /// Set the maximum width of the cell. Can be chained
+    pub fn with_max_width(mut self, max_width: usize) -> Cell {
+        self.set_max_width(Some(max_width));
+        self
+    }
+
+    /// Get the maximum width of the cell, if any
+    pub fn get_max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// Return a copy of the cell whose foreground colors are readable on `background`.
+    /// Cells with their own background color are left unchanged
+    pub (crate) fn adapted_to(&self, background: Background) -> Cell {
+        let mut cell = self.clone();
+        if self.style.iter().any(|a| match *a { Attr::BackgroundColor(_) => true, _ => false }) {
+            return cell;
+        }
+        for a in &mut cell.style {
+            if let Attr::ForegroundColor(c) = *a {
+                *a = Attr::ForegroundColor(readable_color(c, background));
+            }
+        }
+        cell
+    }
+
+    /// Remove all style attributes and reset alignment to default (the column's alignment),
+    /// removing the anchor too
     pub fn reset_style(&mut self) {
         self.style.clear();
-        self.align(Alignment::LEFT);
+        self.align = None;
+        self.anchor = None;
     }
 
     /// Set the cell's style by applying the given specifier string
@@ -178,7 +284,12 @@ impl Cell {
     /// Return the height of the cell
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     pub (crate) fn get_height(&self) -> usize {
-        self.content.len()
+        self.lines.len()
+    }
+
+    /// Return `true` if the cell has more lines than its content because of wrapping
+    pub (crate) fn is_wrapped(&self) -> bool {
+        self.lines.len() > self.content.len()
     }
 
     /// Return the width of the cell
@@ -197,6 +308,39 @@ impl Cell {
         self.hspan
     }
 
+    /// Replace every occurrence of `from` by `to` in the content of the cell, keeping its style,
+    /// alignment and span. Return the number of replacements, which is 0 if `from` is empty
+    pub (crate) fn replace(&mut self, from: &str, to: &str) -> usize {
+        let content = self.get_content();
+        let count = if from.is_empty() { 0 } else { content.matches(from).count() };
+        if count > 0 {
+            self.set_content(&content.replace(from, to));
+        }
+        count
+    }
+
+    /// Set the number of decimals the cell is displayed with when it holds a number,
+    /// overriding the number format of its column
+    pub (crate) fn set_precision(&mut self, decimals: usize) {
+        self.precision = Some(decimals);
+    }
+
+    /// Get the number of decimals the cell is displayed with, if overridden
+    pub (crate) fn get_precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// Set a note about the cell, exported in HTML as a `title` attribute shown when hovering it.
+    /// It is not printed in text
+    pub fn set_annotation(&mut self, text: String) {
+        self.annotation = Some(text);
+    }
+
+    /// Get the note set about the cell, if any
+    pub fn get_annotation(&self) -> Option<&str> {
+        self.annotation.as_ref().map(String::as_str)
+    }
+
     /// Return a copy of the full string contained in the cell
     pub fn get_content(&self) -> String {
         self.content.join("\n")
@@ -205,7 +349,10 @@ impl Cell {
     /// Print a partial cell to `out`. Since the cell may be multi-lined,
     /// `idx` is the line index to print. `col_width` is the column width used to
     /// fill the cells with blanks so it fits in the table.
-    /// If `ìdx` is higher than this cell's height, it will print empty content
+    /// If `ìdx` is higher than this cell's height, it will print empty content.
+    /// `col_align` is the alignment used if none has been set on the cell, and `anchor`
+    /// the position in the column of the anchor string if the cell has one.
+    /// Content wider than `col_width` is truncated
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     pub (crate) fn print<T: Write + ?Sized>(
         &self,
@@ -213,28 +360,67 @@ impl Cell {
         idx: usize,
         col_width: usize,
         skip_right_fill: bool,
+        col_align: Alignment,
+        anchor: usize,
     ) -> Result<(), Error> {
-        let c = self.content.get(idx).map(|s| s.as_ref()).unwrap_or("");
-        print_align(out, self.align, c, ' ', col_width, skip_right_fill)
+        let c = self.lines.get(idx).map(|s| s.as_ref()).unwrap_or("");
+        let (align, pad) = match self.anchor {
+            Some(ref a) if idx < self.lines.len() => (Alignment::LEFT, anchor.saturating_sub(anchor_offset(c, a))),
+            Some(_) => (Alignment::LEFT, 0),
+            None => (self.align.unwrap_or(col_align), 0),
+        };
+        out.write_all(&vec![b' '; pad])?;
+        let width = col_width.saturating_sub(pad);
+        let c = if display_width(c) > width {
+            // Keep the escape sequences of the cut part, so that styles are still reset
+            let kept = truncate(c, width);
+            Cow::Owned(format!("{}{}", kept, escape_sequences(&c[kept.len()..])))
+        } else {
+            Cow::Borrowed(c)
+        };
+        print_align(out, align, &c, ' ', width, skip_right_fill)
     }
 
-    /// Apply style then call `print` to print the cell into a terminal
+    /// Get the string the cell is aligned on: its own anchor, else `col_anchor`, the anchor of its
+    /// column, unless an alignment has been set on the cell
+    pub (crate) fn get_effective_anchor<'a>(&'a self, col_anchor: Option<&'a str>) -> Option<&'a str> {
+        match self.anchor {
+            Some(ref a) => Some(a),
+            None => col_anchor.filter(|_| self.align.is_none()),
+        }
+    }
+
+    /// Return the widest part of the cell's lines before and from `anchor`
+    pub (crate) fn get_anchor_extent(&self, anchor: &str) -> (usize, usize) {
+        self.lines.iter().fold((0, 0), |(before, after), l| {
+            let offset = anchor_offset(l, anchor);
+            (before.max(offset), after.max(display_width(l) - offset))
+        })
+    }
+
+    /// Apply style then call `print` to print the cell into a terminal.
+    /// `continuation` is an additional style applied to all but the first line of the cell
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
+    #[allow(clippy::too_many_arguments)]
     pub (crate) fn print_term<T: Terminal + ?Sized>(
         &self,
         out: &mut T,
         idx: usize,
         col_width: usize,
         skip_right_fill: bool,
+        col_align: Alignment,
+        anchor: usize,
+        continuation: Option<Attr>,
     ) -> Result<(), Error> {
-        for a in &self.style {
+        let continuation = continuation.filter(|_| idx > 0 && idx < self.get_height());
+        for a in self.style.iter().chain(continuation.iter()) {
             match out.attr(*a) {
                 Ok(..) | Err(::term::Error::NotSupported) | Err(::term::Error::ColorOutOfRange) => {
                 } // Ignore unsupported attributes
                 Err(e) => return Err(term_error_to_io_error(e)),
             };
         }
-        self.print(out, idx, col_width, skip_right_fill)?;
+        self.print(out, idx, col_width, skip_right_fill, col_align, anchor)?;
         match out.reset() {
             Ok(..) | Err(::term::Error::NotSupported) | Err(::term::Error::ColorOutOfRange) => {
                 Ok(())
@@ -245,6 +431,36 @@ impl Cell {
 
     /// Print the cell in HTML format to `out`.
     pub fn print_html<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.print_html_aligned(out, Alignment::LEFT)
+    }
+
+    /// Return the cell as a Graphviz HTML-like `<TD>` element, with `col_align` used if no alignment
+    /// has been set on the cell. Content is in bold if `bold` is `true`
+    pub (crate) fn to_graphviz(&self, col_align: Alignment, bold: bool) -> String {
+        let align = match self.align.unwrap_or(col_align) {
+            Alignment::LEFT => "LEFT",
+            Alignment::CENTER => "CENTER",
+            Alignment::RIGHT => "RIGHT",
+        };
+        let colspan = if self.hspan > 1 {
+            format!(" COLSPAN=\"{}\"", self.hspan)
+        } else {
+            String::new()
+        };
+        let content = self.content.iter()
+            .map(|l| HtmlEscape(l).to_string())
+            .collect::<Vec<_>>()
+            .join(&format!("<BR ALIGN=\"{}\"/>", align));
+        if bold && !content.is_empty() {
+            format!("<TD ALIGN=\"{}\"{}><B>{}</B></TD>", align, colspan, content)
+        } else {
+            format!("<TD ALIGN=\"{}\"{}>{}</TD>", align, colspan, content)
+        }
+    }
+
+    /// Print the cell in HTML format to `out`, with `col_align` used if
+    /// no alignment has been set on the cell
+    pub (crate) fn print_html_aligned<T: Write + ?Sized>(&self, out: &mut T, col_align: Alignment) -> Result<usize, Error> {
         /// Convert the color to a hex value useful in CSS
         fn color2hex(color: color::Color) -> &'static str {
             match color {
@@ -297,19 +513,25 @@ impl Cell {
             }
         }
         // Process alignment
-        match self.align {
+        match self.align.unwrap_or(col_align) {
             Alignment::LEFT => styles += "text-align: left;",
             Alignment::CENTER => styles += "text-align: center;",
             Alignment::RIGHT => styles += "text-align: right;",
         }
 
+        let title = match self.annotation {
+            Some(ref a) => format!(" title=\"{}\"", HtmlEscape(a)),
+            None => String::new(),
+        };
+
         let content = self.content.join("<br />");
         out.write_all(
             format!(
-                "<td{1} style=\"{2}\">{0}</td>",
+                "<td{1}{3} style=\"{2}\">{0}</td>",
                 HtmlEscape(&content),
                 colspan,
-                styles
+                styles,
+                title
             )
             .as_bytes(),
         )?;
@@ -340,11 +562,16 @@ impl Default for Cell {
     /// Return a cell initialized with a single empty `String`, with LEFT alignment
     fn default() -> Cell {
         Cell {
-            content: vec!["".to_string(); 1],
+            content: vec![Arc::from(""); 1],
+            lines: vec![Arc::from(""); 1],
             width: 0,
-            align: Alignment::LEFT,
+            max_width: None,
+            align: None,
             style: Vec::new(),
             hspan: 1,
+            precision: None,
+            annotation: None,
+            anchor: None,
         }
     }
 }
@@ -395,6 +622,8 @@ mod tests {
     use crate::format::Alignment;
     use term::{color, Attr};
     use crate::utils::StringWriter;
+    use std::io::Write;
+    use std::sync::Arc;
 
     #[test]
     fn get_content() {
@@ -408,7 +637,7 @@ mod tests {
         assert_eq!(ascii_cell.get_width(), 5);
 
         let mut out = StringWriter::new();
-        let _ = ascii_cell.print(&mut out, 0, 10, false);
+        let _ = ascii_cell.print(&mut out, 0, 10, false, Alignment::LEFT, 0);
         assert_eq!(out.as_string(), "hello     ");
     }
 
@@ -418,7 +647,7 @@ mod tests {
         assert_eq!(unicode_cell.get_width(), 6);
 
         let mut out = StringWriter::new();
-        let _ = unicode_cell.print(&mut out, 0, 10, false);
+        let _ = unicode_cell.print(&mut out, 0, 10, false, Alignment::LEFT, 0);
         assert_eq!(out.as_string(), "привет    ");
     }
 
@@ -427,7 +656,7 @@ mod tests {
         let unicode_cell = Cell::new("由系统自动更新");
         assert_eq!(unicode_cell.get_width(), 14);
         let mut out = StringWriter::new();
-        let _ = unicode_cell.print(&mut out, 0, 20, false);
+        let _ = unicode_cell.print(&mut out, 0, 20, false, Alignment::LEFT, 0);
         assert_eq!(out.as_string(), "由系统自动更新      ");
     }
 
@@ -450,11 +679,24 @@ mod tests {
         assert_eq!(out.as_string(), r#"<td style="text-align: left;">&lt;abc&quot;&gt;&amp;&#39;</td>"#);
     }
 
+    #[test]
+    fn print_html_annotation() {
+        let mut cell = Cell::new("42");
+        cell.set_annotation("<\"avg\" & co>".to_string());
+        assert_eq!(cell.get_annotation(), Some("<\"avg\" & co>"));
+        let mut out = StringWriter::new();
+        let _ = cell.print_html(&mut out);
+        assert_eq!(out.as_string(), r#"<td title="&lt;&quot;avg&quot; &amp; co&gt;" style="text-align: left;">42</td>"#);
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 4, false, Alignment::LEFT, 0);
+        assert_eq!(out.as_string(), "42  ");
+    }
+
     #[test]
     fn align_left() {
         let cell = Cell::new_align("test", Alignment::LEFT);
         let mut out = StringWriter::new();
-        let _ = cell.print(&mut out, 0, 10, false);
+        let _ = cell.print(&mut out, 0, 10, false, Alignment::LEFT, 0);
         assert_eq!(out.as_string(), "test      ");
     }
 
@@ -462,7 +704,7 @@ mod tests {
     fn align_center() {
         let cell = Cell::new_align("test", Alignment::CENTER);
         let mut out = StringWriter::new();
-        let _ = cell.print(&mut out, 0, 10, false);
+        let _ = cell.print(&mut out, 0, 10, false, Alignment::LEFT, 0);
         assert_eq!(out.as_string(), "   test   ");
     }
 
@@ -470,10 +712,28 @@ mod tests {
     fn align_right() {
         let cell = Cell::new_align("test", Alignment::RIGHT);
         let mut out = StringWriter::new();
-        let _ = cell.print(&mut out, 0, 10, false);
+        let _ = cell.print(&mut out, 0, 10, false, Alignment::LEFT, 0);
         assert_eq!(out.as_string(), "      test");
     }
 
+    #[test]
+    fn align_anchor() {
+        let mut cell = Cell::new_align("12 ms\nn/a", Alignment::RIGHT);
+        assert_eq!(cell.get_effective_anchor(Some(" ms")), None);
+        cell.set_anchor(" ms".to_string());
+        assert_eq!(cell.get_anchor(), Some(" ms"));
+        assert_eq!(cell.get_effective_anchor(Some(":")), Some(" ms"));
+        assert_eq!(cell.get_anchor_extent(" ms"), (3, 3));
+        let mut out = StringWriter::new();
+        let _ = cell.print(&mut out, 0, 10, false, Alignment::LEFT, 4);
+        let _ = cell.print(&mut out, 1, 10, false, Alignment::LEFT, 4);
+        let _ = cell.print(&mut out, 2, 10, false, Alignment::LEFT, 4);
+        assert_eq!(out.as_string(), "  12 ms    n/a                ");
+        cell.reset_style();
+        assert_eq!(cell.get_anchor(), None);
+        assert_eq!(cell.get_effective_anchor(Some(":")), Some(":"));
+    }
+
     #[test]
     fn style_spec() {
         let mut cell = Cell::new("test").style_spec("FrBBbuic");
@@ -486,7 +746,7 @@ mod tests {
             cell.style
                 .contains(&Attr::BackgroundColor(color::BRIGHT_BLUE))
         );
-        assert_eq!(cell.align, Alignment::CENTER);
+        assert_eq!(cell.get_alignment(), Alignment::CENTER);
 
         cell = cell.style_spec("FDBwr");
         assert_eq!(cell.style.len(), 2);
@@ -495,7 +755,7 @@ mod tests {
                 .contains(&Attr::ForegroundColor(color::BRIGHT_BLACK))
         );
         assert!(cell.style.contains(&Attr::BackgroundColor(color::WHITE)));
-        assert_eq!(cell.align, Alignment::RIGHT);
+        assert_eq!(cell.get_alignment(), Alignment::RIGHT);
 
         // Test with invalid sepcifier chars
         cell = cell.clone();
@@ -518,16 +778,47 @@ mod tests {
 
         //style_spec("FDBwr");
         assert_eq!(cell.style.len(), 2);
-        assert_eq!(cell.align, Alignment::RIGHT);
+        assert_eq!(cell.get_alignment(), Alignment::RIGHT);
         cell.reset_style();
         assert_eq!(cell.style.len(), 0);
-        assert_eq!(cell.align, Alignment::LEFT);
+        assert_eq!(cell.get_alignment(), Alignment::LEFT);
+    }
+
+    #[test]
+    fn shared() {
+        let s: Arc<str> = Arc::from("shared");
+        let cell = Cell::new_shared(s.clone());
+        assert_eq!(cell, Cell::new("shared"));
+        assert_eq!(Arc::strong_count(&s), 3);
+        assert_eq!(Cell::new_shared(Arc::from("a\nb")), Cell::new("a\nb"));
+        assert_eq!(Cell::new_shared(Arc::from("")), Cell::new(""));
+    }
+
+    #[test]
+    fn max_width_keeps_newlines() {
+        let mut cell = Cell::new("first line\nsecond longer line\n\nlast");
+        assert_eq!(cell.get_height(), 4);
+        assert_eq!(cell.get_width(), 18);
+        cell.set_max_width(Some(8));
+        assert_eq!(cell.get_height(), 7);
+        assert_eq!(cell.get_width(), 6);
+        let mut out = StringWriter::new();
+        for i in 0..cell.get_height() {
+            let _ = cell.print(&mut out, i, 8, false, Alignment::LEFT, 0);
+            out.write_all(b"|").unwrap();
+        }
+        assert_eq!(out.as_string(), "first   |line    |second  |longer  |line    |        |last    |");
+        // Wrapping does not alter the content
+        assert_eq!(cell.get_content(), "first line\nsecond longer line\n\nlast");
+        cell.set_max_width(None);
+        assert_eq!(cell.get_height(), 4);
+        assert_eq!(cell.get_width(), 18);
     }
 
     #[test]
     fn default_empty_cell() {
         let cell = Cell::default();
-        assert_eq!(cell.align, Alignment::LEFT);
+        assert_eq!(cell.get_alignment(), Alignment::LEFT);
         assert!(cell.style.is_empty());
         assert_eq!(cell.get_content(), "");
         assert_eq!(cell.to_string(), "");
@@ -2,23 +2,190 @@
 
 use csv;
 
-pub use self::csv::{Reader, Writer, Result, ReaderBuilder};
+pub use self::csv::{Reader, Writer, Result, ReaderBuilder, WriterBuilder, QuoteStyle, Terminator};
 use std::path::Path;
-use std::io::{Read, Write};
+use std::io::{self, Read, Write};
+use super::TableError;
+
+/// The UTF-8 byte order mark
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+/// Delimiters tried by `Table::from_auto_delimited`, by order of preference
+const SNIFFED_DELIMITERS: [u8; 4] = [b',', b'\t', b';', b'|'];
+
+/// Number of lines sampled by `Table::from_auto_delimited` to detect the delimiter
+const SNIFF_LINES: usize = 10;
+
+/// Format of the data read by `Table::from_reader`
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum InputFormat {
+    /// Comma separated values, the first record holding titles
+    Csv,
+    /// Tab separated values, the first record holding titles
+    Tsv,
+    /// A JSON array of objects, whose keys are titles, or of arrays, the first one holding titles.
+    /// Needs the `json` feature
+    #[cfg(feature = "json")]
+    Json,
+    /// JSON if the `json` feature is enabled and the data starts with `[`, else values separated
+    /// by a delimiter detected like `Table::from_auto_delimited` does, the first record holding titles
+    Auto,
+}
+
+/// Return the delimiter splitting the first lines of `data` into the most columns,
+/// among `SNIFFED_DELIMITERS`, if any splits them into a consistent number of columns
+fn sniff_delimiter(data: &str) -> Option<u8> {
+    let sample = data.lines()
+        .filter(|l| !l.trim().is_empty())
+        .take(SNIFF_LINES)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut best: Option<(u8, usize)> = None;
+    for &d in &SNIFFED_DELIMITERS {
+        match (consistent_field_count(&sample, d), best) {
+            (Some(n), Some((_, m))) if n <= m => {}
+            (Some(n), _) if n > 1 => best = Some((d, n)),
+            _ => {}
+        }
+    }
+    best.map(|(d, _)| d)
+}
+
+/// Return the number of fields of the records of `sample` split on `delimiter`,
+/// if all records have the same number of fields
+fn consistent_field_count(sample: &str, delimiter: u8) -> Option<usize> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(sample.as_bytes());
+    let mut count = None;
+    for record in reader.records() {
+        let len = record.ok()?.len();
+        match count {
+            Some(c) if c != len => return None,
+            _ => count = Some(len),
+        }
+    }
+    count
+}
+
+/// Read the records of `reader`, whose fields are separated by `delimiter`, into rows.
+/// Return `TableError::InconsistentRow` with the index of the first record which doesn't have as
+/// many fields as the first one, or `TableError::Io` if reading fails
+fn read_records<R: Read>(reader: R, delimiter: u8) -> ::std::result::Result<Vec<super::Row>, TableError> {
+    let mut reader = ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(reader);
+    let mut rows: Vec<super::Row> = Vec::new();
+    for (i, record) in reader.records().enumerate() {
+        let record = record.map_err(io::Error::from)?;
+        match rows.first() {
+            Some(r) if r.len() != record.len() => return Err(TableError::InconsistentRow(i)),
+            _ => {}
+        }
+        rows.push(super::Row::new(record.iter().map(super::Cell::new).collect()));
+    }
+    Ok(rows)
+}
+
+/// Return the fields of `row`, one per column it covers: the content of a cell spanning several
+/// columns is followed by empty fields for the other columns
+fn expanded_fields(row: &super::Row) -> Vec<String> {
+    let mut fields = Vec::with_capacity(row.column_count());
+    for c in row.iter() {
+        fields.push(c.get_content());
+        fields.extend((1..c.get_hspan()).map(|_| String::new()));
+    }
+    fields
+}
+
+/// A table rendered both as tab separated values and as HTML, like spreadsheets put it on the clipboard.
+/// Pasting `text` into a spreadsheet preserves columns, while pasting `html` into a rich text editor
+/// preserves styles. See `Table::to_clipboard_formats`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClipboardBundle {
+    /// Tab separated values, quoted like CSV when needed
+    pub text: String,
+    /// HTML table, as written by `print_html`
+    pub html: String,
+}
 
 impl<'a> super::TableSlice<'a> {
+    /// Render the table both as tab separated values and as HTML, to be put on the clipboard
+    pub fn to_clipboard_formats(&self) -> ClipboardBundle {
+        let writer = WriterBuilder::new().delimiter(b'\t').flexible(true).from_writer(Vec::new());
+        let text = self.to_csv_writer(writer)
+            .ok()
+            .and_then(|w| w.into_inner().ok())
+            .expect("Writing to memory cannot fail");
+        let mut html = Vec::new();
+        self.print_html(&mut html).expect("Writing to memory cannot fail");
+        ClipboardBundle {
+            text: String::from_utf8(text).unwrap(),
+            html: String::from_utf8(html).unwrap(),
+        }
+    }
+
     /// Write the table to the specified writer.
     pub fn to_csv<W: Write>(&self, w: W) -> Result<Writer<W>> {
         self.to_csv_writer(Writer::from_writer(w))
     }
 
+    /// Write the table to the specified writer, preceded by the UTF-8 byte order mark
+    /// if `write_bom` is `true`. Some spreadsheet programs need it to detect UTF-8 content.
+    pub fn to_csv_with_bom<W: Write>(&self, mut w: W, write_bom: bool) -> Result<Writer<W>> {
+        if write_bom {
+            w.write_all(UTF8_BOM)?;
+        }
+        self.to_csv(w)
+    }
+
+    /// Write the table to the specified writer as canonical CSV, so that the same table
+    /// always produces the same bytes, whatever the platform. The rules are:
+    ///
+    /// * every field is quoted, and quotes inside fields are doubled
+    /// * every record ends with `\n`
+    /// * no byte order mark is written
+    /// * titles come first if any, then rows in table order
+    /// * every record has as many fields as the table has columns, short rows being padded with empty fields
+    /// * cells spanning several columns are written as a single field, followed by empty fields for the other columns
+    pub fn to_csv_canonical<W: Write>(&self, w: W) -> Result<Writer<W>> {
+        let mut writer = WriterBuilder::new()
+            .quote_style(QuoteStyle::Always)
+            .terminator(Terminator::Any(b'\n'))
+            .from_writer(w);
+        let colnum = self.get_column_num().max(self.titles.as_ref().map(super::Row::column_count).unwrap_or(0));
+        for row in self.titles.iter().chain(self.rows) {
+            let mut fields = expanded_fields(row);
+            fields.resize(colnum, String::new());
+            writer.write_record(&fields)?;
+        }
+        writer.flush()?;
+        Ok(writer)
+    }
+
+    /// Return the SHA-256 digest of the canonical CSV export of the table, written by
+    /// `to_csv_canonical`, as a lowercase hexadecimal string. It only depends on the content
+    /// of titles and cells, and on spans, not on styles, alignments or the table format,
+    /// which makes it a stable key for caching work done on the data.
+    /// Requires the `digest` feature
+    #[cfg(feature = "digest")]
+    pub fn csv_digest(&self) -> ::std::result::Result<String, TableError> {
+        let writer = self.to_csv_canonical(Vec::new()).map_err(io::Error::from)?;
+        let csv = writer.into_inner().map_err(|e| e.into_error())?;
+        Ok(super::sha256::hex_digest(&csv))
+    }
+
     /// Write the table to the specified writer.
     ///
     /// This allows for format customisation.
     pub fn to_csv_writer<W: Write>(&self,
                                 mut writer: Writer<W>)
                                 -> Result<Writer<W>> {
-        for title in self.titles {
+        if let Some(title) = self.titles {
             writer.write_record(title.iter().map(|c| c.get_content()))?;
         }
         for row in self.rows {
@@ -28,6 +195,26 @@ impl<'a> super::TableSlice<'a> {
         writer.flush()?;
         Ok(writer)
     }
+
+    /// Write only the columns at indices `columns`, in this order, to the specified writer.
+    /// A column may be given several times. Rows missing a column get an empty field, and the
+    /// content of a cell spanning several columns is only written in the first one.
+    ///
+    /// Return an error of kind `InvalidInput` if an index is past the number of columns,
+    /// before anything is written
+    pub fn to_csv_columns<W: Write>(&self, mut writer: Writer<W>, columns: &[usize]) -> Result<Writer<W>> {
+        let colnum = self.get_column_num().max(self.titles.as_ref().map(super::Row::column_count).unwrap_or(0));
+        if let Some(c) = columns.iter().find(|&&c| c >= colnum) {
+            let msg = format!("column {} is out of bounds, the table has {} columns", c, colnum);
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, msg).into());
+        }
+        for row in self.titles.iter().chain(self.rows) {
+            let fields = expanded_fields(row);
+            writer.write_record(columns.iter().map(|&c| fields.get(c).map(String::as_str).unwrap_or("")))?;
+        }
+        writer.flush()?;
+        Ok(writer)
+    }
 }
 
 impl super::Table {
@@ -51,6 +238,69 @@ impl super::Table {
                 .from_path(csv_p)?))
     }
 
+    /// Create a table from delimited text, detecting whether fields are separated by commas,
+    /// tabs, semicolons or pipes. The first lines are sampled, and the delimiter splitting them
+    /// into the same number of columns is used. If several delimiters do so, the one yielding
+    /// the most columns wins, then the first of the above list.
+    ///
+    /// Return `TableError::UnknownDelimiter` if no delimiter yields consistent column counts in
+    /// the sampled lines, `TableError::InconsistentRow` with the index of the first record which
+    /// doesn't have as many fields as the first one, or `TableError::Io` if reading fails or
+    /// the input is not valid UTF-8
+    pub fn from_auto_delimited<R: Read>(mut reader: R) -> ::std::result::Result<Self, TableError> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+        let delimiter = sniff_delimiter(&data).ok_or(TableError::UnknownDelimiter)?;
+        Ok(Self::init(read_records(data.as_bytes(), delimiter)?))
+    }
+
+    /// Create a table from data in `format`, whose first record holds the titles.
+    ///
+    /// Return `TableError::InconsistentRow` with the index of the first record, titles included,
+    /// which doesn't have the same columns as the first one, `TableError::UnknownDelimiter` if
+    /// `InputFormat::Auto` can't detect the format, or `TableError::Io` if reading fails or the
+    /// input is invalid
+    pub fn from_reader<R: Read>(mut reader: R, format: InputFormat) -> ::std::result::Result<Self, TableError> {
+        match format {
+            InputFormat::Csv => Self::from_delimited(reader, b',', true),
+            InputFormat::Tsv => Self::from_delimited(reader, b'\t', true),
+            #[cfg(feature = "json")]
+            InputFormat::Json => Self::from_json(reader),
+            InputFormat::Auto => {
+                let mut data = String::new();
+                reader.read_to_string(&mut data)?;
+                #[cfg(feature = "json")]
+                {
+                    if data.trim_start().starts_with('[') {
+                        return Self::from_json(data.as_bytes());
+                    }
+                }
+                let delimiter = sniff_delimiter(&data).ok_or(TableError::UnknownDelimiter)?;
+                Self::from_delimited(data.as_bytes(), delimiter, true)
+            }
+        }
+    }
+
+    /// Create a table from text whose fields are separated by `delimiter`, quoted like CSV.
+    /// If `has_header` is `true`, the first record is used as titles. Otherwise, it's part of
+    /// the data, and titles `Col1`, `Col2`... are generated for each column of the first record.
+    ///
+    /// Return `TableError::InconsistentRow` with the index of the record, titles included,
+    /// if records don't all have the same number of fields, or `TableError::Io` if reading fails
+    pub fn from_delimited<R: Read>(reader: R, delimiter: u8, has_header: bool)
+                                   -> ::std::result::Result<Self, TableError> {
+        let mut rows = read_records(reader, delimiter)?;
+        let titles = if has_header && !rows.is_empty() {
+            rows.remove(0)
+        } else {
+            let count = rows.first().map_or(0, super::Row::len);
+            super::Row::new((1..=count).map(|i| super::Cell::new(&format!("Col{}", i))).collect())
+        };
+        let mut table = Self::init(rows);
+        table.set_titles(titles);
+        Ok(table)
+    }
+
     /// Create a table from a CSV reader
     pub fn from_csv<R: Read>(reader: &mut Reader<R>) -> Self {
         Self::init(reader
@@ -67,21 +317,55 @@ impl super::Table {
     
     /// Write the table to the specified writer.
     pub fn to_csv<W: Write>(&self, w: W) -> Result<Writer<W>> {
-        self.as_ref().to_csv(w)
+        self.as_slice().to_csv(w)
+    }
+
+    /// Render the table both as tab separated values and as HTML, to be put on the clipboard
+    pub fn to_clipboard_formats(&self) -> ClipboardBundle {
+        self.as_slice().to_clipboard_formats()
+    }
+
+    /// Write the table to the specified writer, preceded by the UTF-8 byte order mark
+    /// if `write_bom` is `true`. Some spreadsheet programs need it to detect UTF-8 content.
+    pub fn to_csv_with_bom<W: Write>(&self, w: W, write_bom: bool) -> Result<Writer<W>> {
+        self.as_slice().to_csv_with_bom(w, write_bom)
+    }
+
+    /// Write the table to the specified writer as canonical CSV, so that the same table
+    /// always produces the same bytes, whatever the platform. See `TableSlice::to_csv_canonical`
+    /// for the exact rules
+    pub fn to_csv_canonical<W: Write>(&self, w: W) -> Result<Writer<W>> {
+        self.as_slice().to_csv_canonical(w)
+    }
+
+    /// Return the SHA-256 digest of the canonical CSV export of the table, as a lowercase
+    /// hexadecimal string. Requires the `digest` feature. See `TableSlice::csv_digest`
+    #[cfg(feature = "digest")]
+    pub fn csv_digest(&self) -> ::std::result::Result<String, TableError> {
+        self.as_slice().csv_digest()
     }
 
     /// Write the table to the specified writer.
     ///
     /// This allows for format customisation.
     pub fn to_csv_writer<W: Write>(&self, writer: Writer<W>) -> Result<Writer<W>> {
-        self.as_ref().to_csv_writer(writer)
+        self.as_slice().to_csv_writer(writer)
+    }
+
+    /// Write only the columns at indices `columns`, in this order, to the specified writer,
+    /// leaving the table unchanged. See `TableSlice::to_csv_columns`
+    pub fn to_csv_columns<W: Write>(&self, writer: Writer<W>, columns: &[usize]) -> Result<Writer<W>> {
+        self.as_slice().to_csv_columns(writer, columns)
     }
 }
 
 
 #[cfg(test)]
 mod tests {
-    use crate::{Table, Row, Cell};
+    use crate::{Table, Row, Cell, TableError};
+    use super::{InputFormat, Writer, SNIFF_LINES};
+    use csv::ErrorKind;
+    use std::io;
 
     static CSV_S: &str = "ABC,DEFG,HIJKLMN\n\
                         foobar,bar,foo\n\
@@ -107,6 +391,139 @@ mod tests {
                         .replace("\r\n", "\n"));
     }
 
+    #[test]
+    fn auto_delimited() {
+        for &d in &[",", "\t", ";", "|"] {
+            let input = CSV_S.replace(',', d);
+            let table = Table::from_auto_delimited(input.as_bytes()).unwrap();
+            assert_eq!(table.to_string(), test_table().to_string());
+        }
+        // Commas split lines into 2 columns, semicolons into 3
+        let table = Table::from_auto_delimited("a,b;c;d\ne;f,g;h\n".as_bytes()).unwrap();
+        assert_eq!(table.get_column_num(), 3);
+        assert_eq!(table[0][0].get_content(), "a,b");
+        // Both split lines into 2 columns, commas are preferred
+        let table = Table::from_auto_delimited("a,b;c\nd;e,f\n".as_bytes()).unwrap();
+        assert_eq!(table[0][1].get_content(), "b;c");
+        assert_eq!(Table::from_auto_delimited("a,b,c\nd;e\nf|g|h|i\n".as_bytes()).unwrap_err(),
+                   TableError::UnknownDelimiter);
+        assert_eq!(Table::from_auto_delimited("abc\ndef\n".as_bytes()).unwrap_err(),
+                   TableError::UnknownDelimiter);
+        assert_eq!(Table::from_auto_delimited(&b"a,b\n\xFF,c"[..]).unwrap_err(),
+                   TableError::Io(io::ErrorKind::InvalidData, "stream did not contain valid UTF-8".to_string()));
+        // Records past the sampled lines must have as many fields as the first one too
+        let input = format!("{}x,y,z\n", "a,b\n".repeat(SNIFF_LINES));
+        assert_eq!(Table::from_auto_delimited(input.as_bytes()).unwrap_err(),
+                   TableError::InconsistentRow(SNIFF_LINES));
+    }
+
+    #[test]
+    fn from_reader() {
+        let csv = Table::from_reader(CSV_S.as_bytes(), InputFormat::Csv).unwrap();
+        let tsv = Table::from_reader(CSV_S.replace(',', "\t").as_bytes(), InputFormat::Tsv).unwrap();
+        let auto = Table::from_reader(CSV_S.replace(',', "|").as_bytes(), InputFormat::Auto).unwrap();
+        for table in &[&tsv, &auto] {
+            assert_eq!(table.to_string(), csv.to_string());
+        }
+        assert_eq!(Table::from_reader("a,b\n1\n".as_bytes(), InputFormat::Csv).unwrap_err(),
+                   TableError::InconsistentRow(1));
+        assert_eq!(Table::from_reader("abc\n".as_bytes(), InputFormat::Auto).unwrap_err(),
+                   TableError::UnknownDelimiter);
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn from_reader_json() {
+        let csv = Table::from_reader(CSV_S.as_bytes(), InputFormat::Csv).unwrap();
+        let json = r#"[{"ABC": "foobar", "DEFG": "bar", "HIJKLMN": "foo"},
+                       {"ABC": "foobar2", "DEFG": "bar2", "HIJKLMN": "foo2"}]"#;
+        assert_eq!(Table::from_reader(json.as_bytes(), InputFormat::Json).unwrap().to_string(), csv.to_string());
+        assert_eq!(Table::from_reader(json.as_bytes(), InputFormat::Auto).unwrap().to_string(), csv.to_string());
+        assert_eq!(Table::from_reader(r#"[["a"], [1, 2]]"#.as_bytes(), InputFormat::Auto).unwrap_err(),
+                   TableError::InconsistentRow(1));
+        assert_eq!(Table::from_reader("a,b".as_bytes(), InputFormat::Json).unwrap_err(),
+                   TableError::Io(io::ErrorKind::InvalidData, "Invalid JSON".to_string()));
+    }
+
+    #[test]
+    fn clipboard_formats() {
+        let mut table = test_table();
+        table.add_row(Row::new(vec![Cell::new("a\tb"), Cell::new("c"), Cell::new("d")]));
+        let bundle = table.to_clipboard_formats();
+        assert_eq!(bundle.text, "ABC\tDEFG\tHIJKLMN\nfoobar\tbar\tfoo\nfoobar2\tbar2\tfoo2\n\"a\tb\"\tc\td\n");
+        let mut html = Vec::new();
+        table.print_html(&mut html).unwrap();
+        assert_eq!(bundle.html.as_bytes(), &html[..]);
+        // Rows of different lengths
+        table.add_row(Row::new(vec![Cell::new("e")]));
+        assert!(table.to_clipboard_formats().text.ends_with("\ne\n"));
+    }
+
+    #[test]
+    fn to_canonical() {
+        let mut table = test_table();
+        table.set_titles(Row::new(vec![Cell::new("a"), Cell::new("b")]));
+        table.add_row(Row::new(vec![Cell::new("say \"hi\"\nbye").with_hspan(2)]));
+        let out = table.to_csv_canonical(Vec::new()).unwrap().into_inner().unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(),
+                   "\"a\",\"b\",\"\"\n\
+                    \"ABC\",\"DEFG\",\"HIJKLMN\"\n\
+                    \"foobar\",\"bar\",\"foo\"\n\
+                    \"foobar2\",\"bar2\",\"foo2\"\n\
+                    \"say \"\"hi\"\"\nbye\",\"\",\"\"\n");
+    }
+
+    #[test]
+    fn to_columns() {
+        let mut table = test_table();
+        table.set_titles(Row::new(vec![Cell::new("a"), Cell::new("b"), Cell::new("c")]));
+        table.add_row(Row::new(vec![Cell::new("wide").with_hspan(2)]));
+        let out = table.to_csv_columns(Writer::from_writer(Vec::new()), &[2, 0, 1]).unwrap().into_inner().unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(),
+                   "c,a,b\n\
+                    HIJKLMN,ABC,DEFG\n\
+                    foo,foobar,bar\n\
+                    foo2,foobar2,bar2\n\
+                    ,wide,\n");
+        let err = table.to_csv_columns(Writer::from_writer(Vec::new()), &[0, 3]).unwrap_err();
+        match err.into_kind() {
+            ErrorKind::Io(e) => assert_eq!(e.kind(), io::ErrorKind::InvalidInput),
+            k => panic!("unexpected error {:?}", k),
+        }
+        assert_eq!(table.get_column_num(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "digest")]
+    fn digest() {
+        let mut table = test_table();
+        let digest = table.csv_digest().unwrap();
+        assert_eq!(digest.len(), 64);
+        let csv = table.to_csv_canonical(Vec::new()).unwrap().into_inner().unwrap();
+        assert_eq!(digest, crate::sha256::hex_digest(&csv));
+
+        table.set_format(*crate::format::consts::FORMAT_BOX_CHARS);
+        table[0][0].style(crate::Attr::Bold);
+        assert_eq!(table.csv_digest(), Ok(digest.clone()));
+        table.set_cell(0, 0, "abc").unwrap();
+        assert_ne!(table.csv_digest(), Ok(digest));
+    }
+
+    #[test]
+    fn delimited() {
+        let table = Table::from_delimited("a;b\n1;2\n3;4\n".as_bytes(), b';', true).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.to_csv(Vec::new()).unwrap().into_inner().unwrap(), b"a,b\n1,2\n3,4\n");
+        let table = Table::from_delimited("1,2\n3,4\n".as_bytes(), b',', false).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table.to_csv(Vec::new()).unwrap().into_inner().unwrap(), b"Col1,Col2\n1,2\n3,4\n");
+        assert_eq!(Table::from_delimited("a,b\n1\n".as_bytes(), b',', true).unwrap_err(),
+                   TableError::InconsistentRow(1));
+        assert_eq!(Table::from_delimited("a,b\n1,2,3\n".as_bytes(), b',', false).unwrap_err(),
+                   TableError::InconsistentRow(1));
+        assert!(Table::from_delimited("".as_bytes(), b',', true).unwrap().is_empty());
+    }
+
     #[test]
     fn to() {
         assert_eq!(
@@ -120,6 +537,15 @@ mod tests {
                 CSV_S);
     }
 
+    #[test]
+    fn to_with_bom() {
+        let out = test_table().to_csv_with_bom(Vec::new(), true).unwrap().into_inner().unwrap();
+        assert_eq!(&out[..3], b"\xEF\xBB\xBF");
+        assert_eq!(String::from_utf8(out[3..].to_vec()).unwrap(), CSV_S);
+        let out = test_table().to_csv_with_bom(Vec::new(), false).unwrap().into_inner().unwrap();
+        assert_eq!(String::from_utf8(out).unwrap(), CSV_S);
+    }
+
     #[test]
     fn trans() {
         assert_eq!(
@@ -0,0 +1,100 @@
+//! Conversion of tables into polars `DataFrame`s, enabled with the `polars` feature
+
+use polars::prelude::{Column, DataFrame, NamedFrom, Series};
+
+use super::format::ColumnType;
+use super::{Cell, Table, TableError, TableSlice};
+
+impl<'a> TableSlice<'a> {
+    /// Convert the table into a polars `DataFrame`, with a column per table column. Requires the `polars` feature.
+    ///
+    /// Columns are named after their title, or `column_<index>` if the table has no title for them.
+    /// Their type is guessed like with `TableSlice::infer_column_types`: `Integer` columns become `i64`,
+    /// `Float` ones `f64`, `Bool` ones `bool`, and all others, dates included, strings.
+    /// Blank cells, cells spanning several columns and cells missing from short rows are null.
+    ///
+    /// Return `TableError::DataFrame` if polars rejects the columns, for instance because two titles are equal
+    pub fn to_dataframe(&self) -> Result<DataFrame, TableError> {
+        let columns = self.infer_column_types().into_iter().enumerate().map(|(col, ty)| {
+            let name = self.titles.as_ref()
+                .and_then(|t| t.get_cell_at_column(col))
+                .map(Cell::get_content)
+                .unwrap_or_else(|| format!("column_{}", col));
+            let values: Vec<Option<String>> = self.rows.iter()
+                .map(|r| {
+                    r.get_cell_at_column(col)
+                        .filter(|c| c.get_hspan() == 1)
+                        .map(Cell::get_content)
+                        .filter(|v| !v.trim().is_empty())
+                })
+                .collect();
+            let series = match ty {
+                ColumnType::Integer => {
+                    let values: Vec<Option<i64>> = values.iter()
+                        .map(|v| v.as_ref().and_then(|v| v.trim().parse().ok()))
+                        .collect();
+                    Series::new(name.into(), values)
+                }
+                ColumnType::Float => {
+                    let values: Vec<Option<f64>> = values.iter()
+                        .map(|v| v.as_ref().and_then(|v| v.trim().parse().ok()))
+                        .collect();
+                    Series::new(name.into(), values)
+                }
+                ColumnType::Bool => {
+                    let values: Vec<Option<bool>> = values.iter()
+                        .map(|v| v.as_ref().map(|v| v.trim().eq_ignore_ascii_case("true")))
+                        .collect();
+                    Series::new(name.into(), values)
+                }
+                _ => Series::new(name.into(), values),
+            };
+            Column::from(series)
+        }).collect();
+        DataFrame::new(columns).map_err(|e| TableError::DataFrame(e.to_string()))
+    }
+}
+
+impl Table {
+    /// Convert the table into a polars `DataFrame`. Requires the `polars` feature.
+    /// See `TableSlice::to_dataframe`
+    pub fn to_dataframe(&self) -> Result<DataFrame, TableError> {
+        self.as_slice().to_dataframe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use polars::prelude::DataType;
+
+    use super::*;
+    use crate::Row;
+
+    #[test]
+    fn dataframe_dtypes() {
+        let mut table = Table::init(vec![Row::from(vec!["1", "1.5", "true", "a", "2024-02-29"]),
+                                         Row::from(vec!["-2", "", "FALSE", "b", "2024-03-01"]),
+                                         Row::from(vec!["3", "2e3"])]);
+        table.set_titles(Row::from(vec!["int", "float", "bool", "str"]));
+        let df = table.to_dataframe().unwrap();
+        assert_eq!(df.shape(), (3, 5));
+        let dtypes: Vec<DataType> = df.dtypes();
+        assert_eq!(dtypes, vec![DataType::Int64, DataType::Float64, DataType::Boolean, DataType::String, DataType::String]);
+        assert_eq!(df.get_column_names_str(), vec!["int", "float", "bool", "str", "column_4"]);
+        let float = df.column("float").unwrap().f64().unwrap();
+        assert_eq!(float.get(1), None);
+        assert_eq!(float.get(2), Some(2000.0));
+        assert_eq!(df.column("bool").unwrap().bool().unwrap().get(1), Some(false));
+        assert_eq!(df.column("column_4").unwrap().null_count(), 1);
+    }
+
+    #[test]
+    fn dataframe_duplicate_titles() {
+        let mut table = Table::init(vec![Row::from(vec!["1", "2"])]);
+        table.set_titles(Row::from(vec!["a", "a"]));
+        match table.to_dataframe() {
+            Err(TableError::DataFrame(_)) => {}
+            other => panic!("unexpected result {:?}", other.map(|df| df.shape())),
+        }
+    }
+}
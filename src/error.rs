@@ -0,0 +1,50 @@
+//! Error types returned by table operations
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+/// Error returned by fallible table operations
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TableError {
+    /// The given row index does not exist in the table
+    RowOutOfBounds(usize),
+    /// The given column index is past the number of columns in the table
+    ColumnOutOfBounds(usize),
+    /// Reading the input failed, with the kind and the message of the error
+    Io(io::ErrorKind, String),
+    /// No delimiter splits the input into a consistent number of columns
+    UnknownDelimiter,
+    /// The given row of the input does not have as many columns as the first one
+    InconsistentRow(usize),
+    /// The named border glyph is not exactly one column wide
+    InvalidGlyph(&'static str, char),
+    /// Polars could not build a `DataFrame` from the table, with its message
+    DataFrame(String),
+}
+
+impl fmt::Display for TableError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            TableError::RowOutOfBounds(i) => write!(fmt, "Row {} is out of bounds", i),
+            TableError::ColumnOutOfBounds(i) => write!(fmt, "Column {} is out of bounds", i),
+            TableError::Io(_, ref message) => write!(fmt, "Failed to read input: {}", message),
+            TableError::UnknownDelimiter => write!(fmt, "Could not detect the delimiter"),
+            TableError::InconsistentRow(i) => {
+                write!(fmt, "Row {} does not have the same number of columns as the first row", i)
+            }
+            TableError::InvalidGlyph(name, glyph) => {
+                write!(fmt, "{} glyph {:?} must be one column wide", name, glyph)
+            }
+            TableError::DataFrame(ref message) => write!(fmt, "Failed to build a DataFrame: {}", message),
+        }
+    }
+}
+
+impl Error for TableError {}
+
+impl From<io::Error> for TableError {
+    fn from(e: io::Error) -> TableError {
+        TableError::Io(e.kind(), e.to_string())
+    }
+}
@@ -10,20 +10,21 @@ pub trait EvcxrDisplay {
     fn evcxr_display(&self);
 }
 
-impl<'a, T> EvcxrDisplay for T
+impl<T> EvcxrDisplay for T
 where
-    T: AsRef<TableSlice<'a>>,
+    for<'a> &'a T: Into<TableSlice<'a>>,
 {
     fn evcxr_display(&self) {
+        let slice: TableSlice<'_> = self.into();
         let mut writer = StringWriter::new();
         // Plain Text
         let _ = writer.write_all(b"EVCXR_BEGIN_CONTENT text/plain\n");
-        let _ = self.as_ref().print(&mut writer);
+        let _ = slice.print(&mut writer);
         let _ = writer.write_all(b"\nEVCXR_END_CONTENT\n");
 
         // Html
         let _ = writer.write_all(b"EVCXR_BEGIN_CONTENT text/html\n");
-        let _ = self.as_ref().print_html(&mut writer);
+        let _ = slice.print_html(&mut writer);
         let _ = writer.write_all(b"\nEVCXR_END_CONTENT\n");
         println!("{}", writer.as_string());
     }
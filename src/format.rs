@@ -1,12 +1,19 @@
 //! Define table formatting utilities
 
+use std::fmt;
 use std::io::{Write, Error};
 
 use encode_unicode::Utf8Char;
+use unicode_width::UnicodeWidthChar;
 
-use super::utils::NEWLINE;
+use super::utils::{NEWLINE, format_compact, localize_number, parse_bytes, parse_date, parse_duration};
+use super::{Attr, Row, TableError};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// Alignment for cell's content
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
 pub enum Alignment {
     /// Align left
@@ -17,6 +24,17 @@ pub enum Alignment {
     RIGHT,
 }
 
+/// Vertical alignment of cells which are shorter than their row
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
+pub enum VAlign {
+    /// Content is at the top of the row, blank lines are added below
+    Top,
+    /// Content is in the middle of the row, blank lines are added around
+    Middle,
+    /// Content is at the bottom of the row, blank lines are added above
+    Bottom,
+}
+
 /// Position of a line separator in a table
 #[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
 pub enum LinePosition {
@@ -42,7 +60,223 @@ pub enum ColumnPosition {
     Right,
 }
 
+/// Behavior when a table is wider than the maximum width set with `TableFormat::max_width`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
+pub enum HOverflow {
+    /// Print the table as is, whatever its width
+    None,
+    /// Split columns into several blocks fitting the maximum width, printed one after the other
+    WrapColumns,
+    /// Print only the leading columns which fit, followed by a `→` column
+    /// indicating that more columns exist. The first column is always printed,
+    /// truncated if it doesn't fit by itself
+    Indicator,
+}
+
+/// Sort direction of a column, shown next to its title with `Table::set_sortable_indicator`
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
+pub enum SortState {
+    /// Sorted in ascending order, shown with `▲`
+    Asc,
+    /// Sorted in descending order, shown with `▼`
+    Desc,
+    /// Not sorted, nothing is shown
+    None,
+}
+
+/// Type of the values of a column, used by `Table::sort_view` to compare cells by magnitude
+/// rather than as text, and guessed by `Table::infer_column_types`. See `Table::set_column_type`
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
+pub enum ColumnType {
+    /// Whole numbers like `-42`
+    Integer,
+    /// Decimal numbers like `1.5` or `2e-3`
+    Float,
+    /// `true` or `false`, whatever the case, `false` coming first
+    Bool,
+    /// Dates like `2024-02-29`
+    Date,
+    /// Text, compared as is
+    String,
+    /// Sizes like `1.5 GB`, `200 MiB` or `512`, compared as a number of bytes
+    Bytes,
+    /// Durations like `2h30m`, `45s` or `90`, compared as a number of seconds
+    Duration,
+}
+
+impl ColumnType {
+    /// Parse `text` into a comparable value, or `None` if it is not of this type
+    pub (crate) fn parse(&self, text: &str) -> Option<f64> {
+        let text = text.trim();
+        match *self {
+            ColumnType::Integer => text.parse::<i64>().ok().map(|v| v as f64),
+            ColumnType::Float => text.parse::<f64>().ok().filter(|v| v.is_finite()),
+            ColumnType::Bool if text.eq_ignore_ascii_case("true") => Some(1.0),
+            ColumnType::Bool if text.eq_ignore_ascii_case("false") => Some(0.0),
+            ColumnType::Bool | ColumnType::String => None,
+            ColumnType::Date => parse_date(text),
+            ColumnType::Bytes => parse_bytes(text),
+            ColumnType::Duration => parse_duration(text),
+        }
+    }
+}
+
+impl fmt::Display for ColumnType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            ColumnType::Integer => "integer",
+            ColumnType::Float => "float",
+            ColumnType::Bool => "bool",
+            ColumnType::Date => "date",
+            ColumnType::String => "string",
+            ColumnType::Bytes => "bytes",
+            ColumnType::Duration => "duration",
+        })
+    }
+}
+
+/// What separates titles from the body of a table, see `TableFormat::header_separator`
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
+pub enum HeaderSeparator {
+    /// The title line separator, or the internal one if the format has no title line separator
+    Rule,
+    /// A blank line, padded and with column separators and borders like a row
+    Blank,
+    /// Nothing, the body directly follows the titles
+    None,
+}
+
+/// Strategy used by `Table::fit_to_width` to share the available width between columns
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
+pub enum FitStrategy {
+    /// Give each column a width proportional to its natural width
+    Proportional,
+    /// Choose widths minimizing the total number of extra lines created by wrapping cells
+    Balanced,
+}
+
+/// Symbols used to display numbers, see `NumberFormat::locale`
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq)]
+pub struct NumberLocale {
+    /// Separator of groups of 3 digits, if any
+    thousands: Option<char>,
+    /// Decimal point
+    decimal: char,
+}
+
+impl NumberLocale {
+    /// Create a custom locale, using `thousands` to separate groups of 3 digits and `decimal` as decimal point
+    pub fn new(thousands: Option<char>, decimal: char) -> NumberLocale {
+        NumberLocale { thousands, decimal }
+    }
+
+    /// English convention: `1,234,567.89`
+    pub fn english() -> NumberLocale {
+        NumberLocale::new(Some(','), '.')
+    }
+
+    /// Convention of many European countries, like Germany, Italy or Spain: `1.234.567,89`
+    pub fn european() -> NumberLocale {
+        NumberLocale::new(Some('.'), ',')
+    }
+
+    /// French convention, with a no-break space between groups: `1 234 567,89`
+    pub fn french() -> NumberLocale {
+        NumberLocale::new(Some('\u{a0}'), ',')
+    }
+
+    /// Swiss convention: `1'234'567.89`
+    pub fn swiss() -> NumberLocale {
+        NumberLocale::new(Some('\''), '.')
+    }
+
+    /// Get the separator of groups of 3 digits, if any
+    pub fn get_thousands(&self) -> Option<char> {
+        self.thousands
+    }
+
+    /// Get the decimal point
+    pub fn get_decimal(&self) -> char {
+        self.decimal
+    }
+}
+
+/// Display format of numeric cells of a column, see `Table::set_column_number_format`
+#[derive(Clone, Debug, PartialEq, Copy, Hash, Eq, Default)]
+pub struct NumberFormat {
+    /// Number of significant digits of the compact notation, if enabled
+    compact: Option<usize>,
+    /// Symbols used to display numbers, if any
+    locale: Option<NumberLocale>,
+    /// Number of decimals numbers are rounded to, if any
+    precision: Option<usize>,
+}
+
+impl NumberFormat {
+    /// Create a format displaying numbers as they are stored
+    pub fn new() -> NumberFormat {
+        NumberFormat::default()
+    }
+
+    /// Display numbers of magnitude 1000 and above in compact notation, like `1.2k`, `3.4M`
+    /// or `5.6G`, rounded to `significant` significant digits. Integer digits are never dropped
+    pub fn compact(mut self, significant: usize) -> NumberFormat {
+        self.compact = Some(significant);
+        self
+    }
+
+    /// Get the number of significant digits of the compact notation, if enabled
+    pub fn get_compact(&self) -> Option<usize> {
+        self.compact
+    }
+
+    /// Display numbers using the thousands separator and decimal point of `locale`.
+    /// Only plain decimal numbers like `-1234.5` are localized, other notations are displayed unchanged
+    pub fn locale(mut self, locale: NumberLocale) -> NumberFormat {
+        self.locale = Some(locale);
+        self
+    }
+
+    /// Get the locale used to display numbers, if any
+    pub fn get_locale(&self) -> Option<NumberLocale> {
+        self.locale
+    }
+
+    /// Display numbers rounded to `decimals` decimals. Numbers displayed in compact notation
+    /// are rounded to their significant digits instead
+    pub fn precision(mut self, decimals: usize) -> NumberFormat {
+        self.precision = Some(decimals);
+        self
+    }
+
+    /// Get the number of decimals numbers are rounded to, if any
+    pub fn get_precision(&self) -> Option<usize> {
+        self.precision
+    }
+
+    /// Format `text` for display, or return `None` if it's not a number or is displayed unchanged
+    pub (crate) fn apply(&self, text: &str) -> Option<String> {
+        let value = text.trim().parse::<f64>().ok().filter(|v| v.is_finite())?;
+        let rounded = self.precision.map(|d| format!("{:.*}", d, value));
+        let text = rounded.as_ref().map_or(text, String::as_str);
+        match (self.compact, self.locale) {
+            (Some(significant), locale) if value.abs() >= 1000.0 => {
+                let compact = format_compact(value, significant);
+                match locale {
+                    Some(l) => Some(compact.replace('.', &l.decimal.to_string())),
+                    None => Some(compact),
+                }
+            }
+            (_, Some(l)) => localize_number(text, l.thousands, l.decimal).or(rounded),
+            _ => rounded,
+        }
+    }
+}
+
 /// Contains the character used for printing a line separator
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
 pub struct LineSeparator {
     /// Line separator
@@ -53,6 +287,8 @@ pub struct LineSeparator {
     ljunc: char,
     /// Right junction separator
     rjunc: char,
+    /// Optional junction separator between groups of columns
+    group_junc: Option<char>,
 }
 
 impl LineSeparator {
@@ -64,29 +300,47 @@ impl LineSeparator {
             junc,
             ljunc,
             rjunc,
+            group_junc: None,
         }
     }
 
-    /// Print a full line separator to `out`. `col_width` is a slice containing the width of each column.
+    /// Set the junction used between groups of columns, see `TableFormat::column_group_size`.
+    /// Defaults to the internal junction
+    pub fn group_junction(mut self, junc: char) -> LineSeparator {
+        self.group_junc = Some(junc);
+        self
+    }
+
+    /// Print a full line separator to `out`. `col_width` is a slice containing the width of each column,
+    /// and columns are grouped by `group_size` if not 0. No junction is printed at the start of columns
+    /// whose index is `true` in `spanned`, which are covered by a cell spanning several columns.
     /// Returns the number of printed lines
+    #[allow(clippy::too_many_arguments)]
     fn print<T: Write + ?Sized>(&self,
                                  out: &mut T,
                                  col_width: &[usize],
                                  padding: (usize, usize),
                                  colsep: bool,
                                  lborder: bool,
-                                 rborder: bool)
+                                 rborder: bool,
+                                 group_size: usize,
+                                 spanned: &[bool])
                                  -> Result<usize, Error> {
         if lborder {
             out.write_all(Utf8Char::from(self.ljunc).as_bytes())?;
         }
-        let mut iter = col_width.iter().peekable();
-        while let Some(width) = iter.next() {
+        let mut iter = col_width.iter().enumerate().peekable();
+        while let Some((i, width)) = iter.next() {
             for _ in 0..width + padding.0 + padding.1 {
                 out.write_all(Utf8Char::from(self.line).as_bytes())?;
             }
             if colsep && iter.peek().is_some() {
-                out.write_all(Utf8Char::from(self.junc).as_bytes())?;
+                let junc = match self.group_junc {
+                    _ if spanned.get(i + 1) == Some(&true) => self.line,
+                    Some(g) if group_size > 0 && (i + 1) % group_size == 0 => g,
+                    _ => self.junc,
+                };
+                out.write_all(Utf8Char::from(junc).as_bytes())?;
             }
         }
         if rborder {
@@ -104,6 +358,8 @@ impl Default for LineSeparator {
 }
 
 /// Contains the table formatting rules
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 #[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
 pub struct TableFormat {
     /// Optional column separator character
@@ -126,6 +382,23 @@ pub struct TableFormat {
     pad_right: usize,
     /// Global indentation when rendering the table
     indent: usize,
+    /// Optional maximum width of the rendered table
+    max_width: Option<usize>,
+    /// Behavior when the table is wider than `max_width`
+    h_overflow: HOverflow,
+    /// Optional default alignment of cells
+    align: Option<Alignment>,
+    /// Optional style applied to continuation lines of multiline cells
+    #[cfg_attr(feature = "serde", serde(with = "attr_serde"))]
+    cont_style: Option<Attr>,
+    /// Number of columns per group, or 0 to disable grouping
+    group_size: usize,
+    /// Optional column separator between groups of columns
+    group_csep: Option<char>,
+    /// What separates titles from the body
+    header_sep: HeaderSeparator,
+    /// Whether the last column is padded when there is no right border
+    pad_last: bool,
 }
 
 impl TableFormat {
@@ -142,6 +415,14 @@ impl TableFormat {
             pad_left: 0,
             pad_right: 0,
             indent: 0,
+            max_width: None,
+            h_overflow: HOverflow::None,
+            align: None,
+            cont_style: None,
+            group_size: 0,
+            group_csep: None,
+            header_sep: HeaderSeparator::Rule,
+            pad_last: true,
         }
     }
 
@@ -208,6 +489,28 @@ impl TableFormat {
         }
     }
 
+    /// Set what separates titles from the body. Defaults to `HeaderSeparator::Rule`
+    pub fn header_separator(&mut self, separator: HeaderSeparator) {
+        self.header_sep = separator;
+    }
+
+    /// Get what separates titles from the body
+    pub fn get_header_separator(&self) -> HeaderSeparator {
+        self.header_sep
+    }
+
+    /// Set whether the last column is followed by its right padding when the table has no right
+    /// border. Content is never filled up to the width of this column without right border, so
+    /// disabling it makes lines end right after their last cell. Defaults to `true`
+    pub fn pad_last_column(&mut self, pad: bool) {
+        self.pad_last = pad;
+    }
+
+    /// Get whether the last column is followed by its right padding when the table has no right border
+    pub fn get_pad_last_column(&self) -> bool {
+        self.pad_last
+    }
+
     /// Set global indentation in spaces used when rendering a table
     pub fn indent(&mut self, spaces: usize) {
         self.indent = spaces;
@@ -218,6 +521,82 @@ impl TableFormat {
         self.indent
     }
 
+    /// Set the default alignment of cells which neither set their own alignment,
+    /// nor belong to a column with an alignment
+    pub fn alignment(&mut self, align: Alignment) {
+        self.align = Some(align);
+    }
+
+    /// Get the default alignment of cells, if any
+    pub fn get_alignment(&self) -> Option<Alignment> {
+        self.align
+    }
+
+    /// Set a style applied, when printing to a terminal, to all but the first line of multiline cells,
+    /// for example `Attr::Dim`. It does not change the layout, nor the output without styles
+    pub fn continuation_style(&mut self, style: Attr) {
+        self.cont_style = Some(style);
+    }
+
+    /// Get the style applied to continuation lines of multiline cells, if any
+    pub fn get_continuation_style(&self) -> Option<Attr> {
+        self.cont_style
+    }
+
+    /// Group columns by `size`, the column separator after every `size`th column being drawn with
+    /// the group separator, and line junctions with the group junction of each line separator.
+    /// Grouping is disabled with 0, the default, and has no effect without column separator
+    pub fn column_group_size(&mut self, size: usize) {
+        self.group_size = size;
+    }
+
+    /// Get the number of columns per group, or 0 if grouping is disabled
+    pub fn get_column_group_size(&self) -> usize {
+        self.group_size
+    }
+
+    /// Set the column separator used between groups of columns. Defaults to `‖`
+    pub fn column_group_separator(&mut self, separator: char) {
+        self.group_csep = Some(separator);
+    }
+
+    /// Get the column separator used between groups of columns
+    pub fn get_column_group_separator(&self) -> char {
+        self.group_csep.unwrap_or('‖')
+    }
+
+    /// Set the maximum width of the rendered table, including borders and indentation.
+    /// What happens when the table is wider is configured with `horizontal_overflow`
+    pub fn max_width(&mut self, width: Option<usize>) {
+        self.max_width = width;
+    }
+
+    /// Get the maximum width of the rendered table
+    pub fn get_max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
+    /// Set the behavior when the table is wider than its maximum width
+    pub fn horizontal_overflow(&mut self, overflow: HOverflow) {
+        self.h_overflow = overflow;
+    }
+
+    /// Get the behavior when the table is wider than its maximum width
+    pub fn get_horizontal_overflow(&self) -> HOverflow {
+        self.h_overflow
+    }
+
+    /// Compute the total width of a table whose columns have the width given in `col_width`,
+    /// including padding, separators, borders and indentation
+    pub (crate) fn get_table_width(&self, col_width: &[usize]) -> usize {
+        let (lp, rp) = self.get_padding();
+        let mut width = self.indent + col_width.iter().map(|w| w + lp + rp).sum::<usize>();
+        if self.csep.is_some() && !col_width.is_empty() {
+            width += col_width.len() - 1;
+        }
+        width + self.lborder.map(|_| 1).unwrap_or(0) + self.rborder.map(|_| 1).unwrap_or(0)
+    }
+
     /// Print a full line separator to `out`. `col_width` is a slice containing the width of each column.
     /// Returns the number of printed lines
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
@@ -226,6 +605,39 @@ impl TableFormat {
                                                    col_width: &[usize],
                                                    pos: LinePosition)
                                                    -> Result<usize, Error> {
+        self.__print_line_separator(out, col_width, pos, &[])
+    }
+
+    /// Print a line separator below the row `above`, without junctions under its cells spanning
+    /// several columns
+    pub (crate) fn print_line_separator_below<T: Write + ?Sized>(&self,
+                                                         out: &mut T,
+                                                         col_width: &[usize],
+                                                         pos: LinePosition,
+                                                         above: &Row)
+                                                         -> Result<usize, Error> {
+        let blank = if pos == LinePosition::Intern && above.is_blank_after() {
+            self.print_blank_line(out, col_width)?
+        } else {
+            0
+        };
+        let mut spanned = vec![false; col_width.len()];
+        let mut start = 0;
+        for c in above.iter() {
+            for s in spanned.iter_mut().take(start + c.get_hspan()).skip(start + 1) {
+                *s = true;
+            }
+            start += c.get_hspan();
+        }
+        Ok(blank + self.__print_line_separator(out, col_width, pos, &spanned)?)
+    }
+
+    fn __print_line_separator<T: Write + ?Sized>(&self,
+                                                  out: &mut T,
+                                                  col_width: &[usize],
+                                                  pos: LinePosition,
+                                                  spanned: &[bool])
+                                                  -> Result<usize, Error> {
         match *self.get_sep_for_line(pos) {
             Some(ref l) => {
                 //TODO: Wrap this into dedicated function one day
@@ -235,12 +647,44 @@ impl TableFormat {
                          self.get_padding(),
                          self.csep.is_some(),
                          self.lborder.is_some(),
-                         self.rborder.is_some())
+                         self.rborder.is_some(),
+                         self.group_size,
+                         spanned)
             }
             None => Ok(0),
         }
     }
 
+    /// Print what separates `titles` from the body, as configured with `header_separator`,
+    /// for columns of width `col_width`. Returns the number of printed lines
+    pub (crate) fn print_header_separator<T: Write + ?Sized>(&self,
+                                                     out: &mut T,
+                                                     col_width: &[usize],
+                                                     titles: &Row)
+                                                     -> Result<usize, Error> {
+        match self.header_sep {
+            HeaderSeparator::Rule => self.print_line_separator_below(out, col_width, LinePosition::Title, titles),
+            HeaderSeparator::Blank => self.print_blank_line(out, col_width),
+            HeaderSeparator::None => Ok(0),
+        }
+    }
+
+    /// Print a blank line, padded and with column separators and borders like a row,
+    /// for columns of width `col_width`. Returns the number of printed lines
+    fn print_blank_line<T: Write + ?Sized>(&self, out: &mut T, col_width: &[usize]) -> Result<usize, Error> {
+        out.write_all(&vec![b' '; self.get_indent()])?;
+        self.print_column_separator(out, ColumnPosition::Left)?;
+        for (i, width) in col_width.iter().enumerate() {
+            if i > 0 {
+                self.print_internal_separator(out, i)?;
+            }
+            out.write_all(&vec![b' '; width + self.pad_left + self.pad_right])?;
+        }
+        self.print_column_separator(out, ColumnPosition::Right)?;
+        out.write_all(NEWLINE)?;
+        Ok(1)
+    }
+
     /// Returns the character used to separate columns.
     /// `pos` specify if the separator is left/right final or internal to the table
     pub fn get_column_separator(&self, pos: ColumnPosition) -> Option<char> {
@@ -262,6 +706,20 @@ impl TableFormat {
             None => Ok(()),
         }
     }
+
+    /// Print the internal column separator following `columns` columns,
+    /// which is the group separator at the end of a group of columns
+    pub (crate) fn print_internal_separator<T: Write + ?Sized>(&self,
+                                                       out: &mut T,
+                                                       columns: usize)
+                                                       -> Result<(), Error> {
+        match self.csep {
+            Some(_) if self.group_size > 0 && columns % self.group_size == 0 => {
+                out.write_all(Utf8Char::from(self.get_column_group_separator()).as_bytes())
+            }
+            _ => self.print_column_separator(out, ColumnPosition::Intern),
+        }
+    }
 }
 
 impl Default for TableFormat {
@@ -329,6 +787,54 @@ impl FormatBuilder {
         self
     }
 
+    /// Set the default alignment of cells
+    pub fn alignment(mut self, align: Alignment) -> Self {
+        self.format.alignment(align);
+        self
+    }
+
+    /// Set the style applied to continuation lines of multiline cells
+    pub fn continuation_style(mut self, style: Attr) -> Self {
+        self.format.continuation_style(style);
+        self
+    }
+
+    /// Set whether the last column is followed by its right padding when the table has no right border
+    pub fn pad_last_column(mut self, pad: bool) -> Self {
+        self.format.pad_last_column(pad);
+        self
+    }
+
+    /// Set what separates titles from the body
+    pub fn header_separator(mut self, separator: HeaderSeparator) -> Self {
+        self.format.header_separator(separator);
+        self
+    }
+
+    /// Group columns by `size`, see `TableFormat::column_group_size`
+    pub fn column_group_size(mut self, size: usize) -> Self {
+        self.format.column_group_size(size);
+        self
+    }
+
+    /// Set the column separator used between groups of columns
+    pub fn column_group_separator(mut self, separator: char) -> Self {
+        self.format.column_group_separator(separator);
+        self
+    }
+
+    /// Set the maximum width of the rendered table
+    pub fn max_width(mut self, width: usize) -> Self {
+        self.format.max_width(Some(width));
+        self
+    }
+
+    /// Set the behavior when the table is wider than its maximum width
+    pub fn horizontal_overflow(mut self, overflow: HOverflow) -> Self {
+        self.format.horizontal_overflow(overflow);
+        self
+    }
+
     /// Return the generated `TableFormat`
     pub fn build(&self) -> TableFormat {
         *self.format
@@ -347,6 +853,362 @@ impl From<TableFormat> for FormatBuilder {
     }
 }
 
+/// Set of glyphs used to draw a table's borders, junctions and separators
+///
+/// A `BorderStyle` can be turned into a `TableFormat` with `TableFormat::from`.
+/// Use one of the presets, or a `BorderStyleBuilder` to define a custom style.
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
+pub struct BorderStyle {
+    /// Horizontal edge
+    horizontal: char,
+    /// Vertical edge and column separator
+    vertical: char,
+    /// Top left corner
+    top_left: char,
+    /// Top right corner
+    top_right: char,
+    /// Bottom left corner
+    bottom_left: char,
+    /// Bottom right corner
+    bottom_right: char,
+    /// Junction between the top border and a column separator
+    top_junc: char,
+    /// Junction between the bottom border and a column separator
+    bottom_junc: char,
+    /// Junction between the left border and a line separator
+    left_junc: char,
+    /// Junction between the right border and a line separator
+    right_junc: char,
+    /// Junction between a line separator and a column separator
+    cross: char,
+}
+
+impl BorderStyle {
+    /// Borders made of `-`, `|` and `+`
+    pub fn ascii() -> BorderStyle {
+        BorderStyle {
+            horizontal: '-',
+            vertical: '|',
+            top_left: '+',
+            top_right: '+',
+            bottom_left: '+',
+            bottom_right: '+',
+            top_junc: '+',
+            bottom_junc: '+',
+            left_junc: '+',
+            right_junc: '+',
+            cross: '+',
+        }
+    }
+
+    /// Borders made with box drawing characters
+    pub fn unicode() -> BorderStyle {
+        BorderStyle {
+            horizontal: '─',
+            vertical: '│',
+            top_left: '┌',
+            top_right: '┐',
+            bottom_left: '└',
+            bottom_right: '┘',
+            top_junc: '┬',
+            bottom_junc: '┴',
+            left_junc: '├',
+            right_junc: '┤',
+            cross: '┼',
+        }
+    }
+
+    /// Same as `BorderStyle::unicode()`, with rounded corners
+    pub fn rounded() -> BorderStyle {
+        BorderStyle {
+            top_left: '╭',
+            top_right: '╮',
+            bottom_left: '╰',
+            bottom_right: '╯',
+            ..BorderStyle::unicode()
+        }
+    }
+
+    /// Create a `BorderStyleBuilder` initialized with this style's glyphs
+    pub fn builder(&self) -> BorderStyleBuilder {
+        BorderStyleBuilder { style: *self }
+    }
+}
+
+impl Default for BorderStyle {
+    fn default() -> Self {
+        BorderStyle::ascii()
+    }
+}
+
+impl From<BorderStyle> for TableFormat {
+    fn from(style: BorderStyle) -> TableFormat {
+        FormatBuilder::new()
+            .column_separator(style.vertical)
+            .borders(style.vertical)
+            .separator(LinePosition::Top,
+                       LineSeparator::new(style.horizontal, style.top_junc, style.top_left, style.top_right))
+            .separator(LinePosition::Intern,
+                       LineSeparator::new(style.horizontal, style.cross, style.left_junc, style.right_junc))
+            .separator(LinePosition::Bottom,
+                       LineSeparator::new(style.horizontal, style.bottom_junc, style.bottom_left, style.bottom_right))
+            .padding(1, 1)
+            .build()
+    }
+}
+
+/// A builder to create a custom `BorderStyle`
+///
+/// Glyphs which are not explicitly set are taken from `BorderStyle::ascii()`.
+/// Calling `build()` checks that every glyph occupies exactly one terminal column,
+/// since separators are repeated once per column of width.
+#[derive(Clone, Debug, Copy, Hash, PartialEq, Eq)]
+pub struct BorderStyleBuilder {
+    style: BorderStyle,
+}
+
+impl BorderStyleBuilder {
+    /// Creates a new builder
+    pub fn new() -> BorderStyleBuilder {
+        BorderStyle::ascii().builder()
+    }
+
+    /// Set the glyph used for horizontal edges
+    pub fn horizontal(mut self, glyph: char) -> Self {
+        self.style.horizontal = glyph;
+        self
+    }
+
+    /// Set the glyph used for vertical edges and column separators
+    pub fn vertical(mut self, glyph: char) -> Self {
+        self.style.vertical = glyph;
+        self
+    }
+
+    /// Set the top left corner glyph
+    pub fn top_left(mut self, glyph: char) -> Self {
+        self.style.top_left = glyph;
+        self
+    }
+
+    /// Set the top right corner glyph
+    pub fn top_right(mut self, glyph: char) -> Self {
+        self.style.top_right = glyph;
+        self
+    }
+
+    /// Set the bottom left corner glyph
+    pub fn bottom_left(mut self, glyph: char) -> Self {
+        self.style.bottom_left = glyph;
+        self
+    }
+
+    /// Set the bottom right corner glyph
+    pub fn bottom_right(mut self, glyph: char) -> Self {
+        self.style.bottom_right = glyph;
+        self
+    }
+
+    /// Set the glyph joining the top border with column separators
+    pub fn top_junction(mut self, glyph: char) -> Self {
+        self.style.top_junc = glyph;
+        self
+    }
+
+    /// Set the glyph joining the bottom border with column separators
+    pub fn bottom_junction(mut self, glyph: char) -> Self {
+        self.style.bottom_junc = glyph;
+        self
+    }
+
+    /// Set the glyph joining the left border with line separators
+    pub fn left_junction(mut self, glyph: char) -> Self {
+        self.style.left_junc = glyph;
+        self
+    }
+
+    /// Set the glyph joining the right border with line separators
+    pub fn right_junction(mut self, glyph: char) -> Self {
+        self.style.right_junc = glyph;
+        self
+    }
+
+    /// Set the glyph at the crossing of line and column separators
+    pub fn cross(mut self, glyph: char) -> Self {
+        self.style.cross = glyph;
+        self
+    }
+
+    /// Validate and return the generated `BorderStyle`.
+    ///
+    /// Returns `TableError::InvalidGlyph` naming the first glyph which is not exactly one
+    /// column wide (control, combining or double-width characters).
+    pub fn build(&self) -> Result<BorderStyle, TableError> {
+        let s = &self.style;
+        let glyphs = [
+            (s.horizontal, "Horizontal"),
+            (s.vertical, "Vertical"),
+            (s.top_left, "Top left corner"),
+            (s.top_right, "Top right corner"),
+            (s.bottom_left, "Bottom left corner"),
+            (s.bottom_right, "Bottom right corner"),
+            (s.top_junc, "Top junction"),
+            (s.bottom_junc, "Bottom junction"),
+            (s.left_junc, "Left junction"),
+            (s.right_junc, "Right junction"),
+            (s.cross, "Cross"),
+        ];
+        for &(glyph, name) in glyphs.iter() {
+            if UnicodeWidthChar::width(glyph) != Some(1) {
+                return Err(TableError::InvalidGlyph(name, glyph));
+            }
+        }
+        Ok(self.style)
+    }
+}
+
+impl Default for BorderStyleBuilder {
+    fn default() -> Self {
+        BorderStyleBuilder::new()
+    }
+}
+
+/// A complete appearance for a table: its format (separators, borders, padding,
+/// default alignment and styles) together with per-column alignments.
+///
+/// A theme can be obtained from a table with `Table::theme`, and applied
+/// to any table with `Table::apply_theme`.
+///
+/// With the `serde` feature, themes and the formats they contain implement `Serialize` and
+/// `Deserialize`. Fields missing when deserializing keep the value they have in `TableFormat::new()`.
+#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Theme {
+    #[cfg_attr(feature = "serde", serde(default))]
+    format: TableFormat,
+    #[cfg_attr(feature = "serde", serde(default))]
+    column_align: Vec<Option<Alignment>>,
+}
+
+impl Theme {
+    /// Create a theme using `format`, without per-column alignment
+    pub fn new(format: TableFormat) -> Theme {
+        Theme {
+            format,
+            column_align: Vec::new(),
+        }
+    }
+
+    /// Theme using `consts::FORMAT_DEFAULT`
+    pub fn ascii() -> Theme {
+        Theme::new(*consts::FORMAT_DEFAULT)
+    }
+
+    /// Theme using `consts::FORMAT_BOX_CHARS`
+    pub fn unicode() -> Theme {
+        Theme::new(*consts::FORMAT_BOX_CHARS)
+    }
+
+    /// Theme looking like a markdown table, with a `|---|` line below titles
+    pub fn markdown_like() -> Theme {
+        Theme::new(FormatBuilder::new()
+                       .column_separator('|')
+                       .borders('|')
+                       .separator(LinePosition::Title, LineSeparator::new('-', '|', '|', '|'))
+                       .padding(1, 1)
+                       .build())
+    }
+
+    /// Theme using `consts::FORMAT_CLEAN`
+    pub fn minimal() -> Theme {
+        Theme::new(*consts::FORMAT_CLEAN)
+    }
+
+    /// Set the alignment of cells in column `column` which don't set their own
+    pub fn column_alignment(mut self, column: usize, align: Alignment) -> Self {
+        if column >= self.column_align.len() {
+            self.column_align.resize(column + 1, None);
+        }
+        self.column_align[column] = Some(align);
+        self
+    }
+
+    /// Get the alignment set for column `column`, if any
+    pub fn get_column_alignment(&self, column: usize) -> Option<Alignment> {
+        self.column_align.get(column).cloned().unwrap_or(None)
+    }
+
+    /// Get the format of the theme
+    pub fn get_format(&self) -> &TableFormat {
+        &self.format
+    }
+
+    /// Get a mutable reference to the format of the theme
+    pub fn get_mut_format(&mut self) -> &mut TableFormat {
+        &mut self.format
+    }
+
+    pub (crate) fn from_parts(format: TableFormat, column_align: Vec<Option<Alignment>>) -> Theme {
+        Theme { format, column_align }
+    }
+
+    pub (crate) fn into_parts(self) -> (TableFormat, Vec<Option<Alignment>>) {
+        (self.format, self.column_align)
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme::ascii()
+    }
+}
+
+impl From<TableFormat> for Theme {
+    fn from(format: TableFormat) -> Theme {
+        Theme::new(format)
+    }
+}
+
+impl From<BorderStyle> for Theme {
+    fn from(style: BorderStyle) -> Theme {
+        Theme::new(style.into())
+    }
+}
+
+/// (De)serialization of the terminal attributes of a format, which don't implement serde's traits
+#[cfg(feature = "serde")]
+mod attr_serde {
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::Attr;
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(remote = "Attr")]
+    enum AttrDef {
+        Bold,
+        Dim,
+        Italic(bool),
+        Underline(bool),
+        Blink,
+        Standout(bool),
+        Reverse,
+        Secure,
+        ForegroundColor(u32),
+        BackgroundColor(u32),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct AttrWrapper(#[serde(with = "AttrDef")] Attr);
+
+    pub fn serialize<S: Serializer>(attr: &Option<Attr>, serializer: S) -> Result<S::Ok, S::Error> {
+        attr.map(AttrWrapper).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Attr>, D::Error> {
+        Ok(Option::<AttrWrapper>::deserialize(deserializer)?.map(|AttrWrapper(attr)| attr))
+    }
+}
+
 /// Predifined formats. Those constants are lazily evaluated when
 /// the corresponding struct is dereferenced
 pub mod consts {
@@ -0,0 +1,317 @@
+//! Minimal JSON reader used to import tables
+
+use std::io::{ErrorKind, Read};
+
+use super::{Cell, Row, Table, TableError};
+
+/// Maximum nesting depth of arrays and objects, deeper input is rejected rather than
+/// overflowing the stack
+const MAX_DEPTH: usize = 128;
+
+/// A parsed JSON value. Containers keep their source text, used as content of cells holding them
+enum Value<'a> {
+    Null,
+    /// A string, number or boolean, as displayed in a cell
+    Text(String),
+    Array(Vec<Value<'a>>, &'a str),
+    Object(Vec<(String, Value<'a>)>, &'a str),
+}
+
+impl<'a> Value<'a> {
+    fn into_cell(self) -> Cell {
+        match self {
+            Value::Null => Cell::new(""),
+            Value::Text(t) => Cell::new(&t),
+            Value::Array(_, raw) | Value::Object(_, raw) => Cell::new(raw),
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+    /// Number of arrays and objects containing the current position
+    depth: usize,
+}
+
+fn invalid() -> TableError {
+    TableError::Io(ErrorKind::InvalidData, "Invalid JSON".to_string())
+}
+
+/// Check that `text` is a JSON number, which `f64::from_str` doesn't, accepting `+1` or `inf`
+fn is_number(text: &str) -> bool {
+    !text.starts_with('+')
+        && text.chars().all(|c| c.is_ascii_digit() || "+-.eE".contains(c))
+        && text.parse::<f64>().is_ok()
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<char> {
+        self.text[self.pos..].chars().next()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.pos += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(' ') | Some('\t') | Some('\n') | Some('\r') = self.peek() {
+            self.pos += 1;
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), TableError> {
+        self.skip_whitespace();
+        match self.next() {
+            Some(c) if c == expected => Ok(()),
+            _ => Err(invalid()),
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), TableError> {
+        self.pos += 1;
+        self.depth += 1;
+        if self.depth > MAX_DEPTH {
+            let message = format!("JSON nested deeper than {} levels", MAX_DEPTH);
+            return Err(TableError::Io(ErrorKind::InvalidData, message));
+        }
+        Ok(())
+    }
+
+    fn value(&mut self) -> Result<Value<'a>, TableError> {
+        self.skip_whitespace();
+        let start = self.pos;
+        match self.peek().ok_or_else(invalid)? {
+            '[' => {
+                self.enter()?;
+                let mut items = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some(']') {
+                    self.pos += 1;
+                } else {
+                    loop {
+                        items.push(self.value()?);
+                        self.skip_whitespace();
+                        match self.next() {
+                            Some(',') => {}
+                            Some(']') => break,
+                            _ => return Err(invalid()),
+                        }
+                    }
+                }
+                self.depth -= 1;
+                Ok(Value::Array(items, &self.text[start..self.pos]))
+            }
+            '{' => {
+                self.enter()?;
+                let mut members = Vec::new();
+                self.skip_whitespace();
+                if self.peek() == Some('}') {
+                    self.pos += 1;
+                } else {
+                    loop {
+                        self.expect('"')?;
+                        let key = self.string()?;
+                        self.expect(':')?;
+                        members.push((key, self.value()?));
+                        self.skip_whitespace();
+                        match self.next() {
+                            Some(',') => {}
+                            Some('}') => break,
+                            _ => return Err(invalid()),
+                        }
+                    }
+                }
+                self.depth -= 1;
+                Ok(Value::Object(members, &self.text[start..self.pos]))
+            }
+            '"' => {
+                self.pos += 1;
+                self.string().map(Value::Text)
+            }
+            _ => {
+                while let Some(c) = self.peek().filter(|c| c.is_ascii_alphanumeric() || "+-.".contains(*c)) {
+                    self.pos += c.len_utf8();
+                }
+                match &self.text[start..self.pos] {
+                    "null" => Ok(Value::Null),
+                    t @ "true" | t @ "false" => Ok(Value::Text(t.to_string())),
+                    t if is_number(t) => Ok(Value::Text(t.to_string())),
+                    _ => Err(invalid()),
+                }
+            }
+        }
+    }
+
+    /// Parse the rest of a string whose opening quote has been consumed
+    fn string(&mut self) -> Result<String, TableError> {
+        let mut out = String::new();
+        loop {
+            match self.next().ok_or_else(invalid)? {
+                '"' => return Ok(out),
+                '\\' => match self.next().ok_or_else(invalid)? {
+                    '"' => out.push('"'),
+                    '\\' => out.push('\\'),
+                    '/' => out.push('/'),
+                    'b' => out.push('\u{8}'),
+                    'f' => out.push('\u{c}'),
+                    'n' => out.push('\n'),
+                    'r' => out.push('\r'),
+                    't' => out.push('\t'),
+                    'u' => {
+                        let high = self.hex4()?;
+                        let code = if high >= 0xD800 && high < 0xDC00 {
+                            self.expect('\\')?;
+                            self.expect('u')?;
+                            let low = self.hex4()?;
+                            if low < 0xDC00 || low >= 0xE000 {
+                                return Err(invalid());
+                            }
+                            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+                        } else {
+                            high
+                        };
+                        out.push(std::char::from_u32(code).ok_or_else(invalid)?);
+                    }
+                    _ => return Err(invalid()),
+                },
+                c if (c as u32) < 0x20 => return Err(invalid()),
+                c => out.push(c),
+            }
+        }
+    }
+
+    fn hex4(&mut self) -> Result<u32, TableError> {
+        let digits = self.text.get(self.pos..self.pos + 4).ok_or_else(invalid)?;
+        let code = u32::from_str_radix(digits, 16).map_err(|_| invalid())?;
+        self.pos += 4;
+        Ok(code)
+    }
+}
+
+/// Parse a JSON array of records into titles, if any, and rows.
+///
+/// Records are either objects, whose keys are the titles and must be the same for every record,
+/// or arrays, the first of which holds the titles, and which must all have the same length.
+/// Strings, numbers and booleans are copied into cells, `null` gives an empty cell, and
+/// nested arrays and objects are kept as JSON text.
+///
+/// Return `TableError::InconsistentRow` with the index of the first record which doesn't match
+/// the first one, or `TableError::Io` with `ErrorKind::InvalidData` if the input is not valid
+/// or is nested more than `MAX_DEPTH` levels deep
+pub fn parse_table(text: &str) -> Result<(Option<Row>, Vec<Row>), TableError> {
+    let mut parser = Parser { text, pos: 0, depth: 0 };
+    let records = match parser.value()? {
+        Value::Array(items, _) => items,
+        _ => return Err(invalid()),
+    };
+    parser.skip_whitespace();
+    if parser.pos != text.len() {
+        return Err(invalid());
+    }
+    let mut records = records.into_iter().enumerate();
+    match records.next() {
+        None => Ok((None, Vec::new())),
+        Some((_, Value::Object(first, _))) => {
+            let keys: Vec<String> = first.iter().map(|(k, _)| k.clone()).collect();
+            let titles = Row::new(keys.iter().map(|k| Cell::new(k)).collect());
+            let mut rows = vec![Row::new(first.into_iter().map(|(_, v)| v.into_cell()).collect())];
+            for (i, record) in records {
+                let mut members = match record {
+                    Value::Object(members, _) if members.len() == keys.len() => members,
+                    _ => return Err(TableError::InconsistentRow(i)),
+                };
+                let mut cells = Vec::with_capacity(keys.len());
+                for k in &keys {
+                    let pos = members.iter().position(|(m, _)| m == k).ok_or(TableError::InconsistentRow(i))?;
+                    cells.push(members.swap_remove(pos).1.into_cell());
+                }
+                rows.push(Row::new(cells));
+            }
+            Ok((Some(titles), rows))
+        }
+        Some((_, Value::Array(first, _))) => {
+            let count = first.len();
+            let titles = Row::new(first.into_iter().map(Value::into_cell).collect());
+            let mut rows = Vec::new();
+            for (i, record) in records {
+                match record {
+                    Value::Array(items, _) if items.len() == count => {
+                        rows.push(Row::new(items.into_iter().map(Value::into_cell).collect()))
+                    }
+                    _ => return Err(TableError::InconsistentRow(i)),
+                }
+            }
+            Ok((Some(titles), rows))
+        }
+        Some(_) => Err(invalid()),
+    }
+}
+
+impl Table {
+    /// Create a table from a JSON array of records, which are either objects, whose keys are
+    /// the titles, or arrays, the first one holding the titles. Strings, numbers and booleans
+    /// are copied into cells, `null` gives an empty cell, and nested arrays and objects are kept
+    /// as JSON text.
+    ///
+    /// Return `TableError::InconsistentRow` with the index of the first record which doesn't have
+    /// the same columns as the first one, or `TableError::Io` if reading fails or the input is
+    /// invalid or too deeply nested
+    pub fn from_json<R: Read>(mut reader: R) -> Result<Self, TableError> {
+        let mut data = String::new();
+        reader.read_to_string(&mut data)?;
+        let (titles, rows) = parse_table(&data)?;
+        let mut table = Self::init(rows);
+        if let Some(t) = titles {
+            table.set_titles(t);
+        }
+        Ok(table)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn objects() {
+        let (titles, rows) = parse_table(r#"[{"name": "a\"b", "n": 1.5, "tags": [1, 2]},
+                                             {"n": null, "name": "\u00e9\ud83d\ude00", "tags": {}}]"#).unwrap();
+        assert_eq!(titles.unwrap(), Row::from(vec!["name", "n", "tags"]));
+        assert_eq!(rows[0], Row::from(vec!["a\"b", "1.5", "[1, 2]"]));
+        assert_eq!(rows[1], Row::from(vec!["é😀", "", "{}"]));
+        assert_eq!(parse_table(r#"[{"a": 1}, {"b": 1}]"#).unwrap_err(), TableError::InconsistentRow(1));
+        assert_eq!(parse_table(r#"[{"a": 1}, {"a": 1}, [1]]"#).unwrap_err(), TableError::InconsistentRow(2));
+    }
+
+    #[test]
+    fn arrays() {
+        let (titles, rows) = parse_table("[[\"a\", \"b\"], [true, -2e3]]").unwrap();
+        assert_eq!(titles.unwrap(), Row::from(vec!["a", "b"]));
+        assert_eq!(rows, vec![Row::from(vec!["true", "-2e3"])]);
+        assert_eq!(parse_table("[[\"a\", \"b\"], [1]]").unwrap_err(), TableError::InconsistentRow(1));
+        let (titles, rows) = parse_table(" [ ] ").unwrap();
+        assert!(titles.is_none() && rows.is_empty());
+    }
+
+    #[test]
+    fn invalid_input() {
+        for input in &["", "{}", "[1, 2]", "[[1], ]", "[\"a]", "[[tru]]", "[[1]] x", "[[\"\\x\"]]", "[[+1]]", "[[inf]]", "[[NaN]]"] {
+            assert_eq!(parse_table(input).unwrap_err(), invalid(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn nesting() {
+        let nested = format!("[{}{}]", "[".repeat(MAX_DEPTH - 1), "]".repeat(MAX_DEPTH - 1));
+        assert!(parse_table(&nested).is_ok());
+        let too_deep = format!("[{}{}]", "[".repeat(MAX_DEPTH), "]".repeat(MAX_DEPTH));
+        let message = format!("JSON nested deeper than {} levels", MAX_DEPTH);
+        assert_eq!(parse_table(&too_deep).unwrap_err(), TableError::Io(ErrorKind::InvalidData, message));
+        assert!(parse_table(&"[".repeat(200_000)).is_err());
+        let table = Table::from_json(r#"[{"a": {"b": [1]}}]"#.as_bytes()).unwrap();
+        assert_eq!(table[0][0].get_content(), r#"{"b": [1]}"#);
+    }
+}
@@ -8,36 +8,110 @@
 extern crate lazy_static;
 
 use std::io::{self, Write, Error};
-use std::fmt;
+use std::fmt::{self, Write as FmtWrite};
 use std::iter::{FromIterator, IntoIterator};
 use std::slice::{Iter, IterMut};
 use std::ops::{Index, IndexMut};
+use std::cmp::Ordering;
+use std::mem;
 
 pub use term::{Attr, color};
-pub(crate) use term::{Terminal, stdout};
+pub(crate) use term::{Terminal, stdout, stderr};
 
 mod cell;
 mod row;
+mod error;
+mod schema;
 pub mod format;
 mod utils;
 
 #[cfg(feature = "csv")]
 pub mod csv;
 
+#[cfg(feature = "json")]
+mod json;
+
+#[cfg(feature = "digest")]
+mod sha256;
+
 #[cfg(feature = "evcxr")]
 pub mod evcxr;
 
+#[cfg(feature = "polars")]
+mod dataframe;
+
 pub use row::Row;
 pub use cell::Cell;
-use format::{TableFormat, LinePosition, consts};
-use utils::StringWriter;
+pub use error::TableError;
+pub use schema::{ColumnDef, TableSchema};
+pub use utils::{enable_windows_ansi, CountingWriter};
+use format::{TableFormat, LinePosition, HOverflow, HeaderSeparator, Alignment, VAlign, SortState, ColumnType, FitStrategy, NumberFormat, Theme, consts};
+use utils::{StringWriter, Interner, BarMax, NEWLINE, display_width, print_align, render_bar, fit_proportional, fit_balanced, terminal_background, Background,
+            enable_windows_ansi_once};
+
+/// Row index standing for the titles in the result of `Table::widest_cells`
+pub const TITLE_ROW: usize = std::usize::MAX;
+
+/// Statistics about the rendering of a table, as returned by `Table::render_stats`
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct RenderStats {
+    /// Number of rows, titles excluded
+    pub rows: usize,
+    /// Number of printed columns
+    pub columns: usize,
+    /// Number of bytes emitted by `print`
+    pub bytes: usize,
+    /// Number of cells printed on several lines, wrapped ones included
+    pub multiline_cells: usize,
+    /// Number of cells wrapped because of their maximum width
+    pub wrapped_cells: usize,
+    /// Width of the widest cell
+    pub max_cell_width: usize,
+    /// Width of the widest printed line
+    pub width: usize,
+    /// Number of printed lines
+    pub height: usize,
+}
+
+/// What `print` and `print_term` do when writing to the output fails, see `Table::set_on_error`
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum OnError {
+    /// Stop printing and return the error
+    Abort,
+    /// Flush what has been written so far, ignoring any failure to do so, then stop printing
+    /// and return the error. Partial output isn't left in the buffers of the output
+    FlushAndAbort,
+}
 
 /// An owned printable table
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+///
+/// The `Debug` implementation shows the rendered table. The alternate form (`{:#?}`,
+/// used by `dbg!`) additionally dumps the raw content of the table.
+#[derive(Clone, Hash, PartialEq, Eq)]
 pub struct Table {
     format: Box<TableFormat>,
     titles: Box<Option<Row>>,
     rows: Vec<Row>,
+    column_align: Vec<Option<Alignment>>,
+    column_fixed: Vec<Option<usize>>,
+    column_empty: Vec<Option<String>>,
+    column_number: Vec<Option<NumberFormat>>,
+    column_merge: Vec<bool>,
+    column_valign: Vec<VAlign>,
+    column_sort: Vec<SortState>,
+    column_type: Vec<Option<ColumnType>>,
+    column_icon: Vec<Option<String>>,
+    column_bar: Vec<Option<BarMax>>,
+    column_anchor: Vec<Option<String>>,
+    abs_max_width: Option<usize>,
+    adaptive_colors: bool,
+    flush_per_row: bool,
+    emphasis_stride: usize,
+    on_error: OnError,
+    titles_visible: bool,
+    schema_band: bool,
+    auto_grow: bool,
+    interner: Interner,
 }
 
 /// A borrowed immutable `Table` slice
@@ -59,11 +133,29 @@ pub struct Table {
 /// # }
 /// ```
 ///
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+#[derive(Clone, Hash, PartialEq, Eq)]
 pub struct TableSlice<'a> {
     format: &'a TableFormat,
     titles: &'a Option<Row>,
     rows: &'a [Row],
+    column_align: &'a [Option<Alignment>],
+    column_fixed: &'a [Option<usize>],
+    column_empty: &'a [Option<String>],
+    column_number: &'a [Option<NumberFormat>],
+    column_merge: &'a [bool],
+    column_valign: &'a [VAlign],
+    column_sort: &'a [SortState],
+    column_type: &'a [Option<ColumnType>],
+    column_icon: &'a [Option<String>],
+    column_bar: &'a [Option<BarMax>],
+    column_anchor: &'a [Option<String>],
+    abs_max_width: Option<usize>,
+    adaptive_colors: bool,
+    flush_per_row: bool,
+    emphasis_stride: usize,
+    on_error: OnError,
+    titles_visible: bool,
+    schema_band: bool,
 }
 
 impl<'a> TableSlice<'a> {
@@ -99,8 +191,8 @@ impl<'a> TableSlice<'a> {
     /// Return 0 if the column does not exists;
     fn get_column_width(&self, col_idx: usize) -> usize {
         let mut width = match *self.titles {
-            Some(ref t) => t.get_column_width(col_idx, self.format),
-            None => 0,
+            Some(ref t) if self.titles_visible => t.get_column_width(col_idx, self.format),
+            _ => 0,
         };
         for r in self.rows {
             let l = r.get_column_width(col_idx, self.format);
@@ -108,21 +200,310 @@ impl<'a> TableSlice<'a> {
                 width = l;
             }
         }
-        width
+        let (before, after) = self.get_column_anchor_extent(col_idx);
+        width.max(before + after)
+    }
+
+    /// For each column, get the index of the row whose cell is the widest, and the width of this
+    /// cell, as used to compute the width of the column when printing: cells are measured as they
+    /// are displayed, with default values, number formats, icons and bars. The titles are reported
+    /// as row `TITLE_ROW`. The first row wins ties, titles coming first. A column without any
+    /// cell gets `None`. Cells spanning several columns count for a share of their width in each of them
+    pub fn widest_cells(&self) -> Vec<Option<(usize, usize)>> {
+        let bars = self.get_bar_widths();
+        let titles = self.titles.as_ref()
+            .filter(|_| self.titles_visible)
+            .map(|t| self.display_row(t, false, None, &bars));
+        let rows: Vec<Row> = self.rows.iter().enumerate()
+            .map(|(i, r)| self.display_row(r, true, i.checked_sub(1).map(|p| &self.rows[p]), &bars))
+            .collect();
+        let colnum = rows.iter().chain(titles.iter()).map(Row::column_count).max().unwrap_or(0);
+        (0..colnum).map(|col| {
+            titles.as_ref().map(|t| (TITLE_ROW, t))
+                .into_iter()
+                .chain(rows.iter().enumerate())
+                .filter(|&(_, r)| r.get_cell_at_column(col).is_some())
+                .map(|(i, r)| (i, r.get_column_width(col, self.format)))
+                .fold(None, |widest: Option<(usize, usize)>, (i, w)| match widest {
+                    Some((_, max)) if max >= w => widest,
+                    _ => Some((i, w)),
+                })
+        }).collect()
+    }
+
+    /// Guess the type of the values of each column, titles being counted, from its body cells. A column
+    /// is `Bool`, `Integer`, `Float` or `Date`, tried in this order, if all its non blank cells
+    /// parse as this type, and `String` otherwise or if it has no such cell. Cells spanning
+    /// several columns are ignored. Sizes and durations are not guessed
+    pub fn infer_column_types(&self) -> Vec<ColumnType> {
+        let colnum = self.get_column_num().max(self.titles.as_ref().map_or(0, Row::column_count));
+        (0..colnum).map(|col| {
+            let values: Vec<String> = self.rows.iter()
+                .filter_map(|r| r.get_cell_at_column(col))
+                .filter(|c| c.get_hspan() == 1)
+                .map(Cell::get_content)
+                .filter(|v| !v.trim().is_empty())
+                .collect();
+            [ColumnType::Bool, ColumnType::Integer, ColumnType::Float, ColumnType::Date].iter()
+                .cloned()
+                .find(|ty| !values.is_empty() && values.iter().all(|v| ty.parse(v).is_some()))
+                .unwrap_or(ColumnType::String)
+        }).collect()
+    }
+
+    /// Return the indices of rows in the order they would have if sorted on column `col`,
+    /// without modifying the table. Cells holding numbers are compared numerically and come
+    /// before other cells, which are compared as text. The sort is stable, so equal rows keep
+    /// their order whether `ascending` or not.
+    ///
+    /// If a type is set on the column with `Table::set_column_type`, cells are compared by the
+    /// magnitude they hold, like `1 GB` after `500 MB`, and cells which can't be parsed come last
+    /// in both directions.
+    ///
+    /// Return `TableError::ColumnOutOfBounds` if `col` is past the number of columns
+    pub fn sort_view(&self, col: usize, ascending: bool) -> Result<Vec<usize>, TableError> {
+        if col >= self.get_column_num() {
+            return Err(TableError::ColumnOutOfBounds(col));
+        }
+        let ty = self.column_type.get(col).cloned().unwrap_or(None);
+        let keys: Vec<(Option<f64>, String)> = self.rows.iter().map(|r| {
+            let text = r.get_cell_at_column(col).map(Cell::get_content).unwrap_or_default();
+            let value = match ty {
+                Some(ty) => ty.parse(&text),
+                None => text.trim().parse::<f64>().ok(),
+            };
+            (value.filter(|v| !v.is_nan()), text)
+        }).collect();
+        let mut order: Vec<usize> = (0..keys.len()).collect();
+        order.sort_by(|&a, &b| {
+            let ord = match (keys[a].0, keys[b].0) {
+                (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+                // Unparseable cells of typed columns stay at the end whatever the direction
+                (Some(_), None) if ty.is_some() => return Ordering::Less,
+                (None, Some(_)) if ty.is_some() => return Ordering::Greater,
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => keys[a].1.cmp(&keys[b].1),
+            };
+            if ascending { ord } else { ord.reverse() }
+        });
+        Ok(order)
+    }
+
+    /// Compute statistics about the rendering of the table. The table is printed to
+    /// measure its size, so this costs as much as `print`
+    pub fn render_stats(&self) -> RenderStats {
+        let mut out = Vec::new();
+        // Writing to a vector doesn't fail
+        let height = self.print(&mut out).unwrap_or(0);
+        let text = String::from_utf8_lossy(&out);
+        let titles = self.titles.as_ref().filter(|_| self.titles_visible);
+        let cells: Vec<&Cell> = titles.into_iter().chain(self.rows).flat_map(Row::iter).collect();
+        RenderStats {
+            rows: self.rows.len(),
+            columns: self.get_column_num(),
+            bytes: out.len(),
+            multiline_cells: cells.iter().filter(|c| c.get_height() > 1).count(),
+            wrapped_cells: cells.iter().filter(|c| c.is_wrapped()).count(),
+            max_cell_width: cells.iter().map(|c| c.get_width()).max().unwrap_or(0),
+            width: text.lines().map(display_width).max().unwrap_or(0),
+            height,
+        }
+    }
+
+    /// Get the position of the anchor in column `col_idx` for anchored data cells,
+    /// and the width needed from this anchor to the end of cells
+    fn get_column_anchor_extent(&self, col_idx: usize) -> (usize, usize) {
+        let col_anchor = self.column_anchor.get(col_idx).and_then(Option::as_ref).map(String::as_str);
+        let mut extent = (0, 0);
+        for r in self.rows {
+            if let Some(c) = r.get_cell_at_column(col_idx).filter(|c| c.get_hspan() == 1) {
+                if let Some(a) = c.get_effective_anchor(col_anchor) {
+                    let (before, after) = c.get_anchor_extent(a);
+                    extent = (extent.0.max(before), extent.1.max(after));
+                }
+            }
+        }
+        extent
     }
 
     /// Get the width of all columns, and return a slice
-    /// with the result for each column
+    /// with the result for each column. Widths are capped to the absolute maximum width, if any,
+    /// and columns with a fixed width get exactly this width
     fn get_all_column_width(&self) -> Vec<usize> {
-        let colnum = self.get_column_num();
+        self.get_column_widths(self.get_column_num())
+    }
+
+    /// Get the width of the first `colnum` columns, like `get_all_column_width`
+    fn get_column_widths(&self, colnum: usize) -> Vec<usize> {
         let mut col_width = vec![0usize; colnum];
         for i in 0..colnum {
+            if let Some(&Some(w)) = self.column_fixed.get(i) {
+                col_width[i] = w;
+                continue;
+            }
             // TODO: calling "get_column_width()" in a loop is inefficient
             col_width[i] = self.get_column_width(i);
+            if let Some(max) = self.abs_max_width {
+                col_width[i] = col_width[i].min(max);
+            }
         }
         col_width
     }
 
+    /// Estimate the width of each column from the titles and the first `sample` rows only,
+    /// which is much cheaper than scanning a large table. Cells are measured as they are displayed.
+    /// These are estimates: a wider cell, or a cell in an extra column, past the sample makes the
+    /// exact widths differ. Fixed widths and the maximum column width are applied. The result can
+    /// be given to `Table::set_fixed_widths` or to `render_rows`
+    pub fn estimate_widths(&self, sample: usize) -> Vec<usize> {
+        let head = TableSlice { rows: &self.rows[..sample.min(self.rows.len())], ..*self };
+        let (titles, rows) = head.displayed_rows();
+        let head = TableSlice { titles: &titles, rows: &rows, ..head };
+        let titles = titles.as_ref().filter(|_| self.titles_visible).map_or(0, Row::column_count);
+        head.get_column_widths(head.get_column_num().max(titles))
+    }
+
+    /// Return a copy of `row` as it is displayed: empty cells of the body are replaced with
+    /// their column's default value, numeric cells of the body are formatted with their
+    /// column's number format, cells of merged columns repeating the cell of the `previous`
+    /// row are blanked, titles get the icon of their column, if any, before them, titles of
+    /// sorted columns get their sort indicator, titles get the
+    /// inferred type of their column below them if the schema band is shown, numeric cells of the
+    /// body of columns shown as bars get a bar `bars[col]` wide before them, cells of the body of
+    /// anchored columns get the anchor of their column unless they set their own alignment, and
+    /// cells of columns with a fixed width are clipped to this width
+    fn display_row(&self, row: &Row, body: bool, previous: Option<&Row>, bars: &[usize]) -> Row {
+        let types = if self.schema_band && !body { self.infer_column_types() } else { Vec::new() };
+        let mut cells = row.clone();
+        if body {
+            for (i, default) in self.column_empty.iter().enumerate() {
+                if let Some(ref default) = *default {
+                    if i >= cells.column_count() {
+                        cells.insert_cell_at_column(i, Cell::new(default));
+                    }
+                }
+            }
+        }
+        let mut col = 0;
+        let cells = cells.iter().map(|c| {
+            let empty = self.column_empty.get(col).cloned().unwrap_or(None);
+            let number = self.get_number_format(c, col);
+            let bar = self.column_bar.get(col).cloned().unwrap_or(None).map(|b| (b.0, bars.get(col).cloned().unwrap_or(1)));
+            let fixed = self.column_fixed.get(col).cloned().unwrap_or(None);
+            let align = self.get_effective_alignment(col);
+            let merge = self.column_merge.get(col).cloned().unwrap_or(false);
+            let sort = self.column_sort.get(col).cloned().unwrap_or(SortState::None);
+            let icon = self.column_icon.get(col).and_then(Option::as_ref);
+            let anchor = self.column_anchor.get(col).and_then(Option::as_ref);
+            let above = previous.and_then(|p| p.get_cell_at_column(col)).filter(|a| a.get_hspan() == 1);
+            col += c.get_hspan();
+            let same = above.map(|a| a.get_content() == c.get_content()).unwrap_or(false);
+            let mut c = c.clone();
+            let value = bar.filter(|_| body && c.get_hspan() == 1)
+                .and_then(|b| c.get_content().trim().parse::<f64>().ok().map(|v| (v, b)));
+            if let Some(default) = empty.filter(|_| body && c.get_hspan() == 1) {
+                if c.get_content().trim().is_empty() {
+                    c.set_content(&default);
+                }
+            }
+            if let Some(number) = number.filter(|_| body && c.get_hspan() == 1) {
+                if let Some(text) = number.apply(&c.get_content()) {
+                    c.set_content(&text);
+                }
+            }
+            if let Some((v, (max, width))) = value {
+                let ratio = if max > 0.0 { v / max } else { 0.0 };
+                c.set_content(&format!("{} {}", render_bar(ratio, width), c.get_content().trim()));
+            }
+            if !body && c.get_hspan() == 1 {
+                if let Some(icon) = icon {
+                    c.set_content(&format!("{} {}", icon, c.get_content()));
+                }
+                match sort {
+                    SortState::Asc => c.set_content(&format!("{} ▲", c.get_content())),
+                    SortState::Desc => c.set_content(&format!("{} ▼", c.get_content())),
+                    SortState::None => {}
+                }
+                if let Some(ty) = types.get(col - 1) {
+                    c.set_content(&format!("{}\n({})", c.get_content(), ty));
+                }
+            }
+            if merge && same && c.get_hspan() == 1 {
+                c.set_content("");
+            }
+            if let Some(anchor) = anchor.filter(|_| body && c.get_hspan() == 1) {
+                if let Some(a) = c.get_effective_anchor(Some(anchor)).map(str::to_string) {
+                    c.set_anchor(a);
+                }
+            }
+            match fixed {
+                Some(w) if c.get_hspan() == 1 && c.get_width() > w => c.clipped(w, align),
+                _ => c,
+            }
+        });
+        let mut displayed = Row::new(cells.collect());
+        displayed.set_blank_after(row.is_blank_after());
+        displayed
+    }
+
+    /// Get the number format applied to cell `c` of column `col`: the format of the column, with
+    /// the precision of the cell if it has one
+    fn get_number_format(&self, c: &Cell, col: usize) -> Option<NumberFormat> {
+        let number = self.column_number.get(col).cloned().unwrap_or(None);
+        match c.get_precision() {
+            Some(d) => Some(number.unwrap_or_default().precision(d)),
+            None => number,
+        }
+    }
+
+    /// Get the width of the bars of each column shown as bars, and 0 for other columns.
+    /// See `Table::set_column_as_bar`
+    fn get_bar_widths(&self) -> Vec<usize> {
+        self.column_bar.iter().enumerate().map(|(col, bar)| {
+            if bar.is_none() {
+                return 0;
+            }
+            let mut numbers = 0;
+            let mut others = self.titles.as_ref()
+                .and_then(|t| t.get_cell_at_column(col))
+                .filter(|c| c.get_hspan() == 1)
+                .map_or(0, Cell::get_width);
+            for c in self.rows.iter().filter_map(|r| r.get_cell_at_column(col)).filter(|c| c.get_hspan() == 1) {
+                let content = c.get_content();
+                if content.trim().parse::<f64>().is_ok() {
+                    let text = self.get_number_format(c, col).and_then(|n| n.apply(&content));
+                    numbers = numbers.max(display_width(text.as_ref().map_or(content.trim(), |t| t.trim())));
+                } else {
+                    others = others.max(c.get_width());
+                }
+            }
+            let width = match self.column_fixed.get(col).cloned().unwrap_or(None).or(self.abs_max_width) {
+                Some(w) => w,
+                None => others,
+            };
+            width.saturating_sub(numbers + 1).max(1)
+        }).collect()
+    }
+
+    /// Get the alignment used by cells of column `column` which don't set their own.
+    /// This is the alignment set on the column, else the format's default alignment, else `LEFT`.
+    /// Returns `None` if the column does not exist
+    pub fn get_column_alignment(&self, column: usize) -> Option<Alignment> {
+        let titles_num = self.titles.as_ref().map(Row::column_count).unwrap_or(0);
+        if column >= self.get_column_num().max(titles_num) {
+            return None;
+        }
+        Some(self.get_effective_alignment(column))
+    }
+
+    /// Get the alignment of column `column`, even if it does not exist
+    fn get_effective_alignment(&self, column: usize) -> Alignment {
+        self.column_align.get(column).cloned().unwrap_or(None)
+            .or_else(|| self.format.get_alignment())
+            .unwrap_or(Alignment::LEFT)
+    }
+
     /// Returns an iterator over the immutable cells of the column specified by `column`
     pub fn column_iter(&self, column: usize) -> ColumnIter {
         ColumnIter(self.rows.iter(), column)
@@ -133,45 +514,275 @@ impl<'a> TableSlice<'a> {
         self.rows.iter()
     }
 
+    /// Call `f` with the index of each row and the row itself, stopping at the first error
+    pub fn try_for_each_row<E, F: FnMut(usize, &Row) -> Result<(), E>>(&self, mut f: F) -> Result<(), E> {
+        for (i, r) in self.rows.iter().enumerate() {
+            f(i, r)?;
+        }
+        Ok(())
+    }
+
+    /// Return the `(row, col)` position of the first cell whose content is `needle`, if any.
+    /// Titles are not searched, and `col` is the index of the cell in its row
+    pub fn find_cell(&self, needle: &str) -> Option<(usize, usize)> {
+        self.find_cell_by(|c| c.get_content() == needle)
+    }
+
+    /// Return the `(row, col)` position of the first cell for which `predicate` returns `true`, if any.
+    /// Titles are not searched, and `col` is the index of the cell in its row
+    pub fn find_cell_by<F: FnMut(&Cell) -> bool>(&self, mut predicate: F) -> Option<(usize, usize)> {
+        self.rows.iter()
+            .enumerate()
+            .find_map(|(i, r)| r.iter().position(&mut predicate).map(|j| (i, j)))
+    }
+
+    /// Return the `(row, col)` positions of all the cells whose content is `needle`, row by row.
+    /// Titles are not searched, and `col` is the index of the cell in its row
+    pub fn find_all_cells(&self, needle: &str) -> Vec<(usize, usize)> {
+        self.rows.iter()
+            .enumerate()
+            .flat_map(|(i, r)| r.iter().enumerate().map(move |(j, c)| (i, j, c)))
+            .filter(|(_, _, c)| c.get_content() == needle)
+            .map(|(i, j, _)| (i, j))
+            .collect()
+    }
+
     /// Internal only
     fn __print<T: Write + ?Sized, F>(&self, out: &mut T, f: F) -> Result<usize, Error>
-        where F: Fn(&Row, &mut T, &TableFormat, &[usize]) -> Result<usize, Error>
+        where F: Fn(&Row, &mut T, &TableFormat, &[usize], &[Alignment], &[usize], &[VAlign]) -> Result<usize, Error>
+    {
+        if self.column_fixed.iter().all(Option::is_none)
+            && self.column_empty.iter().all(Option::is_none)
+            && self.column_number.iter().all(Option::is_none)
+            && !self.column_merge.contains(&true)
+            && self.column_sort.iter().all(|s| *s == SortState::None)
+            && self.column_icon.iter().all(Option::is_none)
+            && self.column_bar.iter().all(Option::is_none)
+            && self.column_anchor.iter().all(Option::is_none)
+            && !self.schema_band
+            && !self.rows.iter().any(|r| r.iter().any(|c| c.get_precision().is_some())) {
+            return self.__print_displayed(out, f);
+        }
+        let (titles, rows) = self.displayed_rows();
+        TableSlice {
+            titles: &titles,
+            rows: &rows,
+            ..self.clone()
+        }.__print_displayed(out, f)
+    }
+
+    /// Get the titles and the rows of the table as they are displayed. See `display_row`
+    fn displayed_rows(&self) -> (Option<Row>, Vec<Row>) {
+        let bars = self.get_bar_widths();
+        let titles = self.titles.as_ref().map(|t| self.display_row(t, false, None, &bars));
+        let rows = self.rows.iter().enumerate()
+            .map(|(i, r)| self.display_row(r, true, i.checked_sub(1).map(|p| &self.rows[p]), &bars))
+            .collect();
+        (titles, rows)
+    }
+
+    /// Print the table, once rows have been transformed by `display_row`
+    fn __print_displayed<T: Write + ?Sized, F>(&self, out: &mut T, f: F) -> Result<usize, Error>
+        where F: Fn(&Row, &mut T, &TableFormat, &[usize], &[Alignment], &[usize], &[VAlign]) -> Result<usize, Error>
     {
-        let mut height = 0;
         // Compute columns width
         let col_width = self.get_all_column_width();
+        let max_width = match self.format.get_max_width() {
+            Some(w) if !col_width.is_empty() && self.format.get_table_width(&col_width) > w => w,
+            _ => return self.__print_block(out, &col_width, &f),
+        };
+        match self.format.get_horizontal_overflow() {
+            HOverflow::None => self.__print_block(out, &col_width, &f),
+            HOverflow::WrapColumns => {
+                let mut height = 0;
+                let mut start = 0;
+                while start < col_width.len() {
+                    let mut end = start + 1;
+                    while end < col_width.len()
+                        && self.format.get_table_width(&col_width[start..end + 1]) <= max_width {
+                        end += 1;
+                    }
+                    height += self.__print_projection(out, start, end, None, &f)?;
+                    start = end;
+                }
+                Ok(height)
+            }
+            HOverflow::Indicator => {
+                let mut end = (col_width.len() - 1).max(1);
+                let mut widths = col_width[..end].to_vec();
+                widths.push(1);
+                while end > 1 && self.format.get_table_width(&widths) > max_width {
+                    end -= 1;
+                    widths.remove(end);
+                }
+                self.__print_projection(out, 0, end, Some(max_width), &f)
+            }
+        }
+    }
+
+    /// Print only columns `start` to `end` (excluded). If `indicator` is set, they are followed by
+    /// a column indicating that more columns exist, and the first column is truncated if needed
+    /// so that the table is at most `indicator` wide
+    fn __print_projection<T: Write + ?Sized, F>(&self,
+                                                out: &mut T,
+                                                start: usize,
+                                                end: usize,
+                                                indicator: Option<usize>,
+                                                f: &F)
+                                                -> Result<usize, Error>
+        where F: Fn(&Row, &mut T, &TableFormat, &[usize], &[Alignment], &[usize], &[VAlign]) -> Result<usize, Error>
+    {
+        let project = |r: &Row| {
+            let mut r = r.project(start, end);
+            if indicator.is_some() {
+                while r.column_count() < end - start {
+                    r.add_cell(Cell::default());
+                }
+                r.add_cell(Cell::new("→"));
+            }
+            r
+        };
+        let titles = self.titles.as_ref().map(project);
+        let rows: Vec<Row> = self.rows.iter().map(project).collect();
+        let slice = TableSlice {
+            format: self.format,
+            titles: &titles,
+            rows: &rows,
+            column_align: self.column_align.get(start..).unwrap_or(&[]),
+            column_fixed: self.column_fixed.get(start..).unwrap_or(&[]),
+            column_empty: self.column_empty.get(start..).unwrap_or(&[]),
+            column_number: self.column_number.get(start..).unwrap_or(&[]),
+            column_merge: self.column_merge.get(start..).unwrap_or(&[]),
+            column_valign: self.column_valign.get(start..).unwrap_or(&[]),
+            column_sort: self.column_sort.get(start..).unwrap_or(&[]),
+            column_type: self.column_type.get(start..).unwrap_or(&[]),
+            column_icon: self.column_icon.get(start..).unwrap_or(&[]),
+            column_bar: self.column_bar.get(start..).unwrap_or(&[]),
+            column_anchor: self.column_anchor.get(start..).unwrap_or(&[]),
+            abs_max_width: self.abs_max_width,
+            adaptive_colors: self.adaptive_colors,
+            flush_per_row: self.flush_per_row,
+            emphasis_stride: self.emphasis_stride,
+            on_error: self.on_error,
+            titles_visible: self.titles_visible,
+            schema_band: self.schema_band,
+        };
+        let mut col_width = slice.get_all_column_width();
+        if let Some(max_width) = indicator {
+            let excess = slice.format.get_table_width(&col_width).saturating_sub(max_width);
+            col_width[0] = col_width[0].saturating_sub(excess).max(1);
+        }
+        slice.__print_block(out, &col_width, f)
+    }
+
+    /// Print the table with columns of width `col_width`
+    fn __print_block<T: Write + ?Sized, F>(&self,
+                                           out: &mut T,
+                                           col_width: &[usize],
+                                           f: &F)
+                                           -> Result<usize, Error>
+        where F: Fn(&Row, &mut T, &TableFormat, &[usize], &[Alignment], &[usize], &[VAlign]) -> Result<usize, Error>
+    {
+        let mut height = 0;
         height += self.format
-            .print_line_separator(out, &col_width, LinePosition::Top)?;
+            .print_line_separator(out, col_width, LinePosition::Top)?;
         if let Some(ref t) = *self.titles {
-            height += f(t, out, self.format, &col_width)?;
-            height += self.format
-                .print_line_separator(out, &col_width, LinePosition::Title)?;
+            let col_align: Vec<Alignment> = (0..col_width.len()).map(|i| self.get_effective_alignment(i)).collect();
+            let blank;
+            let t = if self.titles_visible {
+                t
+            } else {
+                blank = t.blanked();
+                &blank
+            };
+            // Titles are not anchored
+            height += f(t, out, self.format, col_width, &col_align, &[], self.column_valign)?;
+            height += self.format.print_header_separator(out, col_width, t)?;
         }
-        // Print rows
+        height += self.__print_rows(out, col_width, f)?;
+        height += match self.rows.last() {
+            Some(r) => self.format.print_line_separator_below(out, col_width, LinePosition::Bottom, r)?,
+            None => self.format.print_line_separator(out, col_width, LinePosition::Bottom)?,
+        };
+        out.flush()?;
+        Ok(height)
+    }
+
+    /// Print only the rows of the table, separated by internal lines, with columns of width `col_width`
+    fn __print_rows<T: Write + ?Sized, F>(&self,
+                                          out: &mut T,
+                                          col_width: &[usize],
+                                          f: &F)
+                                          -> Result<usize, Error>
+        where F: Fn(&Row, &mut T, &TableFormat, &[usize], &[Alignment], &[usize], &[VAlign]) -> Result<usize, Error>
+    {
+        let col_align: Vec<Alignment> = (0..col_width.len()).map(|i| self.get_effective_alignment(i)).collect();
+        let col_anchor: Vec<usize> = (0..col_width.len()).map(|i| self.get_column_anchor_extent(i).0).collect();
+        let mut height = 0;
         let mut iter = self.rows.iter().peekable();
         while let Some(r) = iter.next() {
-            height += f(r, out, self.format, &col_width)?;
+            height += f(r, out, self.format, col_width, &col_align, &col_anchor, self.column_valign)?;
+            if self.flush_per_row {
+                out.flush()?;
+            }
             if iter.peek().is_some() {
                 height += self.format
-                    .print_line_separator(out, &col_width, LinePosition::Intern)?;
+                    .print_line_separator_below(out, col_width, LinePosition::Intern, r)?;
             }
         }
-        height += self.format
-            .print_line_separator(out, &col_width, LinePosition::Bottom)?;
-        out.flush()?;
         Ok(height)
     }
 
     /// Print the table to `out` and returns the number of
     /// line printed, or an error
     pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
-        self.__print(out, Row::print)
+        let result = self.__print(out, Row::print);
+        self.on_print_result(out, result)
+    }
+
+    /// Flush `out` if printing to it failed and the table is set to `OnError::FlushAndAbort`,
+    /// then return `result`
+    fn on_print_result<T: Write + ?Sized>(&self, out: &mut T, result: Result<usize, Error>) -> Result<usize, Error> {
+        if result.is_err() && self.on_error == OnError::FlushAndAbort {
+            let _ = out.flush();
+        }
+        result
     }
 
     /// Print the table to terminal `out`, applying styles when needed and returns the number of
     /// line printed, or an error
     pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
-        self.__print(out, Row::print_term)
+        let background = if self.adaptive_colors { terminal_background() } else { None };
+        self.print_term_on(out, background)
+    }
+
+    /// Print the table to terminal `out` like `print_term`, `background` being the detected
+    /// background of the terminal, if any. It is ignored unless adaptive colors are enabled
+    fn print_term_on<T: Terminal + ?Sized>(&self, out: &mut T, background: Option<Background>) -> Result<usize, Error> {
+        let n = self.emphasis_stride;
+        if n > 0 && self.rows.len() >= n {
+            let rows: Vec<Row> = self.rows.iter().enumerate().map(|(i, r)| {
+                let mut r = r.clone();
+                if (i + 1) % n == 0 {
+                    for c in r.iter_mut() {
+                        c.style(Attr::Bold);
+                    }
+                }
+                r
+            }).collect();
+            return TableSlice {
+                rows: &rows,
+                emphasis_stride: 0,
+                ..self.clone()
+            }.print_term_on(out, background);
+        }
+        let result = match background.filter(|_| self.adaptive_colors) {
+            Some(bg) => self.__print(out, |r, out, format, width, align, anchor, valign| {
+                r.adapted_to(bg).print_term(out, format, width, align, anchor, valign)
+            }),
+            None => self.__print(out, Row::print_term),
+        };
+        self.on_print_result(out, result)
     }
 
     /// Print the table to standard output. Colors won't be displayed unless
@@ -180,11 +791,16 @@ impl<'a> TableSlice<'a> {
     /// output is redirected to a file, or piped to another program, the output is considered
     /// as not beeing tty, and ANSI escape characters won't be displayed unless `force colorize`
     /// is set to `true`.
+    /// On Windows, the processing of ANSI escape characters by the console is enabled the first
+    /// time colors are displayed, see `enable_windows_ansi()`.
     /// # Returns
     /// A `Result` holding the number of lines printed, or an `io::Error` if any failure happens
     pub fn print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
         match (stdout(), atty::is(atty::Stream::Stdout) || force_colorize) {
-            (Some(mut o), true) => self.print_term(&mut *o),
+            (Some(mut o), true) => {
+                enable_windows_ansi_once();
+                self.print_term(&mut *o)
+            }
             _ => self.print(&mut io::stdout()),
         }
     }
@@ -199,27 +815,353 @@ impl<'a> TableSlice<'a> {
         let _ = self.print_tty(false); // Ignore result
     }
 
+    /// Print the table to standard error. Colors are displayed only if stderr is a tty terminal.
+    /// This keeps standard output free for other data, like a machine readable result.
+    /// Any failure to print is ignored, like with `printstd()`.
+    pub fn printstderr(&self) {
+        let _ = match (stderr(), atty::is(atty::Stream::Stderr)) {
+            (Some(mut o), true) => {
+                enable_windows_ansi_once();
+                self.print_term(&mut *o)
+            }
+            _ => self.print(&mut io::stderr()),
+        };
+    }
+
     /// Print table in HTML format to `out`.
+    /// Only alignment and cell styles are used: separators, padding, indentation and widths
+    /// of the table format only apply to text rendering
     pub fn print_html<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
         // Compute column width
         let column_num = self.get_column_num();
+        let col_align: Vec<Alignment> = (0..column_num).map(|i| self.get_effective_alignment(i)).collect();
         out.write_all(b"<table>")?;
         // Print titles / table header
         if let Some(ref t) = *self.titles {
             out.write_all(b"<th>")?;
-            t.print_html(out, column_num)?;
+            t.print_html_aligned(out, column_num, &col_align)?;
             out.write_all(b"</th>")?;
         }
         // Print rows
         for r in self.rows {
             out.write_all(b"<tr>")?;
-            r.print_html(out, column_num)?;
+            r.print_html_aligned(out, column_num, &col_align)?;
             out.write_all(b"</tr>")?;
         }
         out.write_all(b"</table>")?;
         out.flush()?;
         Ok(())
     }
+
+    /// Print the table to `out` as a GitHub Flavored Markdown table. Columns are aligned with
+    /// `:` markers according to their alignment, `|` in cells is escaped and line breaks are
+    /// written as `<br>`. A cell spanning several columns is followed by empty cells, and a
+    /// table without titles gets an empty header row, which Markdown requires.
+    /// If `pretty` is `true`, cells are padded to the width of their column, on the side given
+    /// by its alignment, so that the Markdown source is aligned too. Both render the same
+    pub fn print_markdown<T: Write + ?Sized>(&self, out: &mut T, pretty: bool) -> Result<(), Error> {
+        let colnum = self.get_column_num().max(self.titles.as_ref().map_or(0, Row::column_count));
+        if colnum == 0 {
+            return Ok(());
+        }
+        let fields = |r: &Row| {
+            let mut fields = Vec::with_capacity(colnum);
+            for c in r.iter() {
+                fields.push(c.get_content().replace('|', "\\|").replace('\n', "<br>"));
+                fields.extend((1..c.get_hspan()).map(|_| String::new()));
+            }
+            fields.resize(colnum, String::new());
+            fields
+        };
+        let header = self.titles.as_ref().map_or_else(|| vec![String::new(); colnum], fields);
+        let body: Vec<Vec<String>> = self.rows.iter().map(fields).collect();
+        let col_align: Vec<Alignment> = (0..colnum).map(|i| self.get_effective_alignment(i)).collect();
+        // Delimiter cells need at least 3 characters to hold `:-:`
+        let widths: Vec<usize> = (0..colnum).map(|i| if pretty {
+            body.iter().chain(Some(&header)).map(|f| display_width(&f[i])).max().unwrap_or(0).max(3)
+        } else {
+            3
+        }).collect();
+        let print_line = |out: &mut T, fields: &[String]| -> Result<(), Error> {
+            out.write_all(b"|")?;
+            for (i, f) in fields.iter().enumerate() {
+                out.write_all(b" ")?;
+                if pretty {
+                    print_align(out, col_align[i], f, ' ', widths[i], false)?;
+                } else {
+                    out.write_all(f.as_bytes())?;
+                }
+                out.write_all(b" |")?;
+            }
+            out.write_all(NEWLINE)
+        };
+        print_line(out, &header)?;
+        let delimiters: Vec<String> = col_align.iter().zip(&widths).map(|(align, &w)| match *align {
+            Alignment::LEFT => "-".repeat(w),
+            Alignment::CENTER => format!(":{}:", "-".repeat(w - 2)),
+            Alignment::RIGHT => format!("{}:", "-".repeat(w - 1)),
+        }).collect();
+        print_line(out, &delimiters)?;
+        for f in &body {
+            print_line(out, f)?;
+        }
+        out.flush()
+    }
+
+    /// Return the table as a Graphviz HTML-like label, to use as `label=<...>` in a node
+    /// definition. Titles are in bold, cells are aligned like in the table, and lines of
+    /// multiline cells are separated with `<BR ALIGN="..."/>`, carrying the alignment of the cell
+    /// so that every line is aligned. Styles other than bold titles are not rendered
+    pub fn to_graphviz_label(&self) -> String {
+        let column_num = self.get_column_num();
+        let col_align: Vec<Alignment> = (0..column_num).map(|i| self.get_effective_alignment(i)).collect();
+        let to_tr = |r: &Row, bold: bool| {
+            let mut col = 0;
+            let cells: String = r.iter().map(|c| {
+                let align = col_align.get(col).cloned().unwrap_or(Alignment::LEFT);
+                col += c.get_hspan();
+                c.to_graphviz(align, bold)
+            }).collect();
+            format!("<TR>{}</TR>", cells)
+        };
+        let mut label = String::from("<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\">");
+        if let Some(t) = self.titles.as_ref() {
+            label += &to_tr(t, true);
+        }
+        for r in self.rows {
+            label += &to_tr(r, false);
+        }
+        label + "</TABLE>"
+    }
+
+    /// Print the table rotated, each row being printed as a block of `title: value` lines,
+    /// with a rule between blocks. Titles are padded so colons are aligned.
+    /// Columns without title are labelled with their index.
+    /// Returns the number of lines printed, or an error
+    pub fn print_rotated<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        let column_num = self.rows.iter().map(Row::len).max().unwrap_or(0);
+        let labels: Vec<String> = (0..column_num)
+            .map(|i| match self.titles.as_ref().and_then(|t| t.get_cell(i)) {
+                Some(c) => c.get_content().replace('\n', " "),
+                None => i.to_string(),
+            })
+            .collect();
+        let label_width = labels.iter().map(|l| display_width(l)).max().unwrap_or(0);
+        let value_width = self.rows.iter()
+            .flat_map(|r| r.iter())
+            .map(Cell::get_width)
+            .max()
+            .unwrap_or(0);
+        let mut height = 0;
+        for (n, r) in self.rows.iter().enumerate() {
+            if n > 0 {
+                out.write_all(&vec![b'-'; label_width + 2 + value_width])?;
+                out.write_all(NEWLINE)?;
+                height += 1;
+            }
+            for (i, label) in labels.iter().enumerate() {
+                let content = r.get_cell(i).map(Cell::get_content).unwrap_or_default();
+                let mut lines = content.lines();
+                print_align(out, Alignment::LEFT, label, ' ', label_width, false)?;
+                out.write_all(b": ")?;
+                out.write_all(lines.next().unwrap_or("").as_bytes())?;
+                out.write_all(NEWLINE)?;
+                height += 1;
+                for l in lines {
+                    out.write_all(&vec![b' '; label_width + 2])?;
+                    out.write_all(l.as_bytes())?;
+                    out.write_all(NEWLINE)?;
+                    height += 1;
+                }
+            }
+        }
+        out.flush()?;
+        Ok(height)
+    }
+
+    /// Print each row to `out` by substituting its cells into `template`, followed by a newline.
+    /// Placeholders are column titles between braces, like `"{Name} is {Age} years old"`,
+    /// and `{{` and `}}` stand for literal braces. Cells missing from a row are replaced by nothing.
+    /// Fails with `ErrorKind::InvalidInput`, before printing anything, if a placeholder isn't
+    /// the title of a column or isn't closed
+    pub fn print_templated<W: Write + ?Sized>(&self, out: &mut W, template: &str) -> Result<(), Error> {
+        let invalid = |msg: String| Error::new(io::ErrorKind::InvalidInput, msg);
+        // Literal text, each followed by the index of the column to substitute, if any
+        let mut pieces: Vec<(String, Option<usize>)> = Vec::new();
+        let mut text = String::new();
+        let mut chars = template.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => return Err(invalid(format!("Unclosed placeholder {{{}", name))),
+                        }
+                    }
+                    let col = self.titles.as_ref()
+                        .and_then(|t| t.iter().position(|c| c.get_content() == name))
+                        .ok_or_else(|| invalid(format!("Unknown column {:?} in template", name)))?;
+                    pieces.push((mem::replace(&mut text, String::new()), Some(col)));
+                }
+                c => text.push(c),
+            }
+        }
+        pieces.push((text, None));
+        for r in self.rows {
+            for (text, col) in &pieces {
+                out.write_all(text.as_bytes())?;
+                if let Some(c) = col.and_then(|c| r.get_cell(c)) {
+                    out.write_all(c.get_content().as_bytes())?;
+                }
+            }
+            out.write_all(NEWLINE)?;
+        }
+        out.flush()
+    }
+
+    /// Return a copy of `row` where occurrences of `needle` in cells are surrounded by `before` and `after`
+    fn highlight_row(row: &Row, needle: &str, before: &str, after: &str) -> Row {
+        let replacement = format!("{}{}{}", before, needle, after);
+        let mut row = row.clone();
+        for c in row.iter_mut() {
+            if !needle.is_empty() && c.get_content().contains(needle) {
+                c.set_content(&c.get_content().replace(needle, &replacement));
+            }
+        }
+        row
+    }
+
+    /// Print the table with occurrences of `needle` surrounded by `before` and `after`.
+    /// Rows are highlighted once displayed, so that number formats and the other display settings
+    /// apply to the original content
+    fn __print_highlighted<T: Write + ?Sized, F>(&self, out: &mut T, needle: &str, before: &str, after: &str, f: F)
+                                                 -> Result<usize, Error>
+        where F: Fn(&Row, &mut T, &TableFormat, &[usize], &[Alignment], &[usize], &[VAlign]) -> Result<usize, Error>
+    {
+        let (titles, rows) = self.displayed_rows();
+        let titles = titles.map(|t| Self::highlight_row(&t, needle, before, after));
+        let rows: Vec<Row> = rows.iter().map(|r| Self::highlight_row(r, needle, before, after)).collect();
+        TableSlice {
+            titles: &titles,
+            rows: &rows,
+            ..self.clone()
+        }.__print_displayed(out, f)
+    }
+
+    /// Print rows from `start_row` onward to `out`, with columns of width `widths`, without titles
+    /// nor top and bottom lines. Each row but the first of the table is preceded by an internal line.
+    /// This allows appending rows to the output of a previous rendering which had no bottom line,
+    /// given the widths it used. Cells wider than their column are truncated
+    pub fn append_rows_to_writer<W: Write + ?Sized>(&self, out: &mut W, start_row: usize, widths: &[usize])
+                                                    -> Result<(), Error> {
+        let col_align: Vec<Alignment> = (0..widths.len()).map(|i| self.get_effective_alignment(i)).collect();
+        let col_anchor: Vec<usize> = (0..widths.len()).map(|i| self.get_column_anchor_extent(i).0).collect();
+        let bars = self.get_bar_widths();
+        for (i, r) in self.rows.iter().enumerate().skip(start_row) {
+            if i > 0 {
+                self.format.print_line_separator_below(out, widths, LinePosition::Intern, &self.rows[i - 1])?;
+            }
+            let r = self.display_row(r, true, i.checked_sub(1).map(|p| &self.rows[p]), &bars);
+            r.project(0, widths.len()).print(out, self.format, widths, &col_align, &col_anchor, self.column_valign)?;
+        }
+        out.flush()
+    }
+
+    /// Print only the header block to `out`, with columns of width `widths` : the top line,
+    /// then the titles followed by the title line if the table has titles.
+    /// Together with `append_rows_to_writer`, this allows printing a table incrementally.
+    /// Cells wider than their column are truncated
+    pub fn print_header<W: Write + ?Sized>(&self, out: &mut W, widths: &[usize]) -> Result<(), Error> {
+        self.format.print_line_separator(out, widths, LinePosition::Top)?;
+        if let Some(t) = self.titles.as_ref() {
+            let col_align: Vec<Alignment> = (0..widths.len()).map(|i| self.get_effective_alignment(i)).collect();
+            let t = self.display_row(t, false, None, &[]);
+            let t = if self.titles_visible { t.project(0, widths.len()) } else { t.blanked() };
+            // Titles are not anchored
+            t.print(out, self.format, widths, &col_align, &[], self.column_valign)?;
+            self.format.print_header_separator(out, widths, &t)?;
+        }
+        out.flush()
+    }
+
+    /// Print `rows`, which don't need to belong to the table, to `out` as a table with columns
+    /// of width `widths`, using the format and column settings of this table: rows are displayed
+    /// like its own rows, with default values, number formats, merged cells and bars. The titles
+    /// of this table are printed first if `titles` is `true`, blanked if they are hidden.
+    /// Rows are borrowed rather than copied into a new table, which suits displaying views of
+    /// data kept elsewhere. Cells wider than their column are truncated
+    pub fn render_rows<'b, W, I>(&self, out: &mut W, rows: I, widths: &[usize], titles: bool) -> Result<(), Error>
+        where W: Write + ?Sized,
+              I: IntoIterator<Item = &'b Row>
+    {
+        let col_align: Vec<Alignment> = (0..widths.len()).map(|i| self.get_effective_alignment(i)).collect();
+        let col_anchor: Vec<usize> = (0..widths.len()).map(|i| self.get_column_anchor_extent(i).0).collect();
+        let bars = self.get_bar_widths();
+        self.format.print_line_separator(out, widths, LinePosition::Top)?;
+        if let Some(t) = self.titles.as_ref().filter(|_| titles) {
+            let t = self.display_row(t, false, None, &bars);
+            let t = if self.titles_visible { t.project(0, widths.len()) } else { t.blanked() };
+            // Titles are not anchored
+            t.print(out, self.format, widths, &col_align, &[], self.column_valign)?;
+            self.format.print_header_separator(out, widths, &t)?;
+        }
+        let mut previous: Option<&Row> = None;
+        let mut last: Option<Row> = None;
+        for r in rows {
+            let displayed = self.display_row(r, true, previous, &bars).project(0, widths.len());
+            if let Some(ref above) = last {
+                self.format.print_line_separator_below(out, widths, LinePosition::Intern, above)?;
+            }
+            displayed.print(out, self.format, widths, &col_align, &col_anchor, self.column_valign)?;
+            previous = Some(r);
+            last = Some(displayed);
+        }
+        match last {
+            Some(r) => self.format.print_line_separator_below(out, widths, LinePosition::Bottom, &r)?,
+            None => self.format.print_line_separator(out, widths, LinePosition::Bottom)?,
+        };
+        out.flush()
+    }
+
+    /// Print the table to `out`, passing each rendered line, borders included but without its
+    /// line ending, to `hook` along with its index, and writing what `hook` returns instead.
+    /// This allows numbering, prefixing or indenting lines. The table is rendered before
+    /// anything is written
+    pub fn print_with_line_hook<W, F>(&self, out: &mut W, mut hook: F) -> Result<(), Error>
+        where W: Write + ?Sized,
+              F: FnMut(usize, &str) -> String
+    {
+        let mut rendered = StringWriter::new();
+        self.print(&mut rendered)?;
+        for (i, line) in rendered.as_string().lines().enumerate() {
+            out.write_all(hook(i, line).as_bytes())?;
+            out.write_all(NEWLINE)?;
+        }
+        out.flush()
+    }
+
+    /// Print the table to `out`, with every occurrence of `needle` in cells surrounded
+    /// by `>>` and `<<` markers. Markers are part of the width of cells
+    pub fn print_highlighted<W: Write + ?Sized>(&self, out: &mut W, needle: &str) -> Result<(), Error> {
+        self.__print_highlighted(out, needle, ">>", "<<", Row::print).map(|_| ())
+    }
+
+    /// Print the table to terminal `out`, with every occurrence of `needle` in cells displayed
+    /// in reverse video using ANSI escape codes. The width of cells is unaffected
+    pub fn print_term_highlighted<T: Terminal + ?Sized>(&self, out: &mut T, needle: &str) -> Result<(), Error> {
+        self.__print_highlighted(out, needle, "\x1b[7m", "\x1b[27m", Row::print_term).map(|_| ())
+    }
 }
 
 impl<'a> IntoIterator for &'a TableSlice<'a> {
@@ -242,6 +1184,56 @@ impl Table {
             rows,
             titles: Box::new(None),
             format: Box::new(*consts::FORMAT_DEFAULT),
+            column_align: Vec::new(),
+            column_fixed: Vec::new(),
+            column_empty: Vec::new(),
+            column_number: Vec::new(),
+            column_merge: Vec::new(),
+            column_valign: Vec::new(),
+            column_sort: Vec::new(),
+            column_type: Vec::new(),
+            column_icon: Vec::new(),
+            column_bar: Vec::new(),
+            column_anchor: Vec::new(),
+            abs_max_width: None,
+            adaptive_colors: false,
+            flush_per_row: false,
+            emphasis_stride: 0,
+            on_error: OnError::Abort,
+            titles_visible: true,
+            schema_band: false,
+            auto_grow: false,
+            interner: Interner::default(),
+        }
+    }
+
+    /// Get a borrowed `TableSlice` covering the whole table
+    ///
+    /// `Table` doesn't implement `AsRef<TableSlice>`: a `TableSlice` borrows the table, so there
+    /// is none to return a reference to. Use this method, or `TableSlice::from(&table)`, instead
+    pub fn as_slice(&self) -> TableSlice<'_> {
+        TableSlice {
+            format: &self.format,
+            titles: &self.titles,
+            rows: &self.rows,
+            column_align: &self.column_align,
+            column_fixed: &self.column_fixed,
+            column_empty: &self.column_empty,
+            column_number: &self.column_number,
+            column_merge: &self.column_merge,
+            column_valign: &self.column_valign,
+            column_sort: &self.column_sort,
+            column_type: &self.column_type,
+            column_icon: &self.column_icon,
+            column_bar: &self.column_bar,
+            column_anchor: &self.column_anchor,
+            abs_max_width: self.abs_max_width,
+            adaptive_colors: self.adaptive_colors,
+            flush_per_row: self.flush_per_row,
+            emphasis_stride: self.emphasis_stride,
+            on_error: self.on_error,
+            titles_visible: self.titles_visible,
+            schema_band: self.schema_band,
         }
     }
 
@@ -255,11 +1247,10 @@ impl Table {
         &mut self.format
     }
 
-    /// Compute and return the number of column
-    // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
-    #[cfg(test)] // Only used for testing for now
-    pub (crate) fn get_column_num(&self) -> usize {
-        self.as_ref().get_column_num()
+    /// Compute and return the number of column, including the titles row
+    fn get_column_num(&self) -> usize {
+        let titles_num = self.titles.as_ref().as_ref().map(Row::column_count).unwrap_or(0);
+        self.as_slice().get_column_num().max(titles_num)
     }
 
     /// Get the number of rows
@@ -282,6 +1273,29 @@ impl Table {
         *self.titles = None;
     }
 
+    /// Show or hide titles, shown by default. Hidden titles are kept, and still used by
+    /// exports, but are printed as a blank line above the title separator, without
+    /// widening columns. Unlike `unset_titles`, the top structure of the table is unchanged
+    pub fn set_titles_visible(&mut self, visible: bool) {
+        self.titles_visible = visible;
+    }
+
+    /// Check if titles are shown when printing
+    pub fn get_titles_visible(&self) -> bool {
+        self.titles_visible
+    }
+
+    /// Show the type of each column, as guessed by `infer_column_types`, on a line below its
+    /// title, like `(integer)`. This band is part of the titles, and is not shown without titles
+    pub fn set_schema_band(&mut self, show: bool) {
+        self.schema_band = show;
+    }
+
+    /// Check if the type of each column is shown below its title
+    pub fn get_schema_band(&self) -> bool {
+        self.schema_band
+    }
+
     /// Get a mutable reference to a row
     pub fn get_mut_row(&mut self, row: usize) -> Option<&mut Row> {
         self.rows.get_mut(row)
@@ -300,11 +1314,110 @@ impl Table {
         &mut self.rows[l]
     }
 
+    /// Check if `row` can be added to the table with `try_add_row`, without adding it.
+    /// When the table has titles, a row must not have more columns than the titles, and
+    /// `TableError::ColumnOutOfBounds` is returned with the index of the first extra column,
+    /// unless columns are grown automatically (see `set_auto_grow_columns`)
+    pub fn can_add_row(&self, row: &Row) -> Result<(), TableError> {
+        match *self.titles {
+            Some(ref t) if !self.auto_grow && row.column_count() > t.column_count() => {
+                Err(TableError::ColumnOutOfBounds(t.column_count()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Append `row` to the table if `can_add_row` accepts it, and return a mutable reference to it.
+    /// Unlike `add_row`, rows with more columns than the titles are rejected
+    pub fn try_add_row(&mut self, row: Row) -> Result<&mut Row, TableError> {
+        self.can_add_row(&row)?;
+        self.grow_columns(row.column_count());
+        Ok(self.add_row(row))
+    }
+
+    /// Enable or disable automatic growing of columns, disabled by default. When enabled,
+    /// `try_add_row` accepts rows with more columns than the titles, and widens the table instead:
+    /// titles of new columns are generated as `Column N`, `N` being the 1-based index of the column,
+    /// and existing rows are padded with empty cells
+    pub fn set_auto_grow_columns(&mut self, auto_grow: bool) {
+        self.auto_grow = auto_grow;
+    }
+
+    /// Check if automatic growing of columns is enabled
+    pub fn get_auto_grow_columns(&self) -> bool {
+        self.auto_grow
+    }
+
+    /// Widen the table to `count` columns if columns are grown automatically and titles are shorter
+    fn grow_columns(&mut self, count: usize) {
+        let titles = match *self.titles {
+            Some(ref mut t) if self.auto_grow && count > t.column_count() => t,
+            _ => return,
+        };
+        for i in titles.column_count()..count {
+            titles.add_cell(Cell::new(&format!("Column {}", i + 1)));
+        }
+        for r in &mut self.rows {
+            for _ in r.column_count()..count {
+                r.add_cell(Cell::default());
+            }
+        }
+    }
+
     /// Append an empty row in the table. Return a mutable reference to this new row.
     pub fn add_empty_row(&mut self) -> &mut Row {
         self.add_row(Row::default())
     }
 
+    /// Append rows until the table has at least `n` rows, each cell of which contains `fill`.
+    /// Filler rows have as many cells as the table has columns, titles included
+    pub fn pad_to_rows(&mut self, n: usize, fill: &str) {
+        let colnum = self.get_column_num();
+        while self.rows.len() < n {
+            self.rows.push(Row::new(vec![Cell::new(fill); colnum]));
+        }
+    }
+
+    /// Check that every row covers as many columns as the table has, titles included,
+    /// cells spanning several columns counting for each of them
+    pub fn is_rectangular(&self) -> bool {
+        let colnum = self.get_column_num();
+        self.rows.iter().all(|r| r.column_count() == colnum)
+    }
+
+    /// Append cells containing `fill` to rows covering fewer columns than the table has,
+    /// titles included, so that the table is rectangular. Since the number of columns is the one
+    /// of the widest row or of the titles, no row is ever longer. Returns the number of rows altered
+    pub fn make_rectangular(&mut self, fill: &str) -> usize {
+        let colnum = self.get_column_num();
+        let mut altered = 0;
+        for r in &mut self.rows {
+            let count = r.column_count();
+            if count < colnum {
+                for _ in count..colnum {
+                    r.add_cell(Cell::new(fill));
+                }
+                altered += 1;
+            }
+        }
+        altered
+    }
+
+    /// Append a row made of `values`, and return a mutable reference to this row.
+    /// Cells with the same content in rows added with this method share their storage,
+    /// which saves memory in large tables with few distinct values.
+    ///
+    /// Each distinct value stays cached in the table until it is dropped, even after the rows
+    /// using it are removed, so avoid this method for unbounded streams of distinct values
+    pub fn add_row_interned<I, S>(&mut self, values: I) -> &mut Row
+        where I: IntoIterator<Item = S>,
+              S: AsRef<str>
+    {
+        let interner = &mut self.interner;
+        let cells = values.into_iter().map(|v| Cell::new_shared(interner.intern(v.as_ref()))).collect();
+        self.add_row(Row::new(cells))
+    }
+
     /// Insert `row` at the position `index`, and return a mutable reference to this row.
     /// If index is higher than current numbers of rows, `row` is appended at the end of the table
     pub fn insert_row(&mut self, index: usize, row: Row) -> &mut Row {
@@ -316,685 +1429,3359 @@ impl Table {
         }
     }
 
+    /// Insert a column at position `index`, shifting the following columns to the right.
+    /// `title` is inserted in the titles row, if any, and every row gets a cell containing `default`.
+    /// Rows shorter than `index` are first padded with empty cells.
+    /// Returns an error if `index` is greater than the number of columns
+    pub fn insert_column(&mut self, index: usize, title: &str, default: &str) -> Result<(), TableError> {
+        if index > self.get_column_num() {
+            return Err(TableError::ColumnOutOfBounds(index));
+        }
+        if let Some(ref mut t) = *self.titles {
+            t.insert_cell_at_column(index, Cell::new(title));
+        }
+        for r in &mut self.rows {
+            r.insert_cell_at_column(index, Cell::new(default));
+        }
+        if index < self.column_align.len() {
+            self.column_align.insert(index, None);
+        }
+        if index < self.column_fixed.len() {
+            self.column_fixed.insert(index, None);
+        }
+        if index < self.column_empty.len() {
+            self.column_empty.insert(index, None);
+        }
+        if index < self.column_number.len() {
+            self.column_number.insert(index, None);
+        }
+        if index < self.column_merge.len() {
+            self.column_merge.insert(index, false);
+        }
+        if index < self.column_valign.len() {
+            self.column_valign.insert(index, VAlign::Top);
+        }
+        if index < self.column_sort.len() {
+            self.column_sort.insert(index, SortState::None);
+        }
+        if index < self.column_type.len() {
+            self.column_type.insert(index, None);
+        }
+        if index < self.column_icon.len() {
+            self.column_icon.insert(index, None);
+        }
+        if index < self.column_bar.len() {
+            self.column_bar.insert(index, None);
+        }
+        if index < self.column_anchor.len() {
+            self.column_anchor.insert(index, None);
+        }
+        Ok(())
+    }
+
+    /// Append a column at the end of the table, with `title` in the titles row, if any,
+    /// and a cell containing `default` in every row
+    pub fn add_column(&mut self, title: &str, default: &str) {
+        let index = self.get_column_num();
+        // Cannot fail, since the index is the number of columns
+        let _ = self.insert_column(index, title, default);
+    }
+
+    /// Append a column at the end of the table, with `title` in the titles row, if any,
+    /// and in every row a cell containing the result of `f` applied to this row.
+    /// `f` is called on rows before the column is added
+    pub fn add_computed_column<F: Fn(&Row) -> String>(&mut self, title: String, f: F) {
+        let values: Vec<String> = self.rows.iter().map(f).collect();
+        self.add_column(&title, "");
+        for (r, v) in self.rows.iter_mut().zip(values) {
+            // The new column is the last cell of every row
+            let last = r.len() - 1;
+            r[last] = Cell::new(&v);
+        }
+    }
+
     /// Modify a single element in the table
+    #[deprecated(since = "0.9.0", note = "Use `set_cell`, which takes the row before the column")]
     pub fn set_element(&mut self, element: &str, column: usize, row: usize) -> Result<(), &str> {
         let rowline = self.get_mut_row(row).ok_or("Cannot find row")?;
         // TODO: If a cell already exist, copy it's alignment parameter
         rowline.set_cell(Cell::new(element), column)
     }
 
-    /// Remove the row at position `index`. Silently skip if the row does not exist
-    pub fn remove_row(&mut self, index: usize) {
-        if index < self.rows.len() {
-            self.rows.remove(index);
-        }
+    /// Replace the content of the cell at row `row` and column `col` with `value`.
+    /// Returns an error if the row or the cell does not exist
+    pub fn set_cell<T: ToString>(&mut self, row: usize, col: usize, value: T) -> Result<(), TableError> {
+        let rowline = self.rows.get_mut(row).ok_or(TableError::RowOutOfBounds(row))?;
+        rowline.set_cell(Cell::new(&value.to_string()), col)
+            .map_err(|_| TableError::ColumnOutOfBounds(col))
     }
 
-    /// Return an iterator over the immutable cells of the column specified by `column`
-    pub fn column_iter(&self, column: usize) -> ColumnIter {
-        ColumnIter(self.rows.iter(), column)
+    /// Make the cell at row `row` and grid column `col` span `span` columns. The cells it covers
+    /// are removed, and the internal separators between the covered columns are not printed for
+    /// this row. Spanning a cell which already spans several columns changes its span in place,
+    /// columns it no longer covers getting empty cells. A row shorter than `col` is first padded
+    /// with empty cells.
+    /// Returns an error if the row does not exist, or if the span exceeds the number of columns
+    pub fn set_cell_span(&mut self, row: usize, col: usize, span: usize) -> Result<(), TableError> {
+        let span = span.max(1);
+        let colnum = self.get_column_num();
+        let rowline = self.rows.get_mut(row).ok_or(TableError::RowOutOfBounds(row))?;
+        if col + span > colnum {
+            return Err(TableError::ColumnOutOfBounds(col + span - 1));
+        }
+        rowline.span_at_column(col, span);
+        Ok(())
     }
 
-    /// Return an iterator over the mutable cells of the column specified by `column`
-    pub fn column_iter_mut(&mut self, column: usize) -> ColumnIterMut {
-        ColumnIterMut(self.rows.iter_mut(), column)
+    /// Display the number in the cell at row `row` and grid column `col` with `decimals` decimals,
+    /// whatever the precision of the number format of its column, if any, whose other settings
+    /// still apply. Cells spanning several columns are not formatted.
+    /// Returns an error if the row or the cell does not exist
+    pub fn set_cell_precision(&mut self, row: usize, col: usize, decimals: usize) -> Result<(), TableError> {
+        let rowline = self.rows.get_mut(row).ok_or(TableError::RowOutOfBounds(row))?;
+        let cell = rowline.get_mut_cell_at_column(col).ok_or(TableError::ColumnOutOfBounds(col))?;
+        cell.set_precision(decimals);
+        Ok(())
     }
 
-    /// Returns an iterator over immutable rows
-    pub fn row_iter(&self) -> Iter<Row> {
-        self.rows.iter()
+    /// Attach the note `text` to the cell at row `row` and grid column `col`. In HTML, it is exported
+    /// as a `title` attribute shown when hovering the cell. It is not printed in text.
+    /// Returns an error if the row or the cell does not exist
+    pub fn set_cell_annotation(&mut self, row: usize, col: usize, text: String) -> Result<(), TableError> {
+        let rowline = self.rows.get_mut(row).ok_or(TableError::RowOutOfBounds(row))?;
+        let cell = rowline.get_mut_cell_at_column(col).ok_or(TableError::ColumnOutOfBounds(col))?;
+        cell.set_annotation(text);
+        Ok(())
     }
 
-    /// Returns an iterator over mutable rows
-    pub fn row_iter_mut(&mut self) -> IterMut<Row> {
-        self.rows.iter_mut()
+    /// Print a blank line, with borders and column separators, between row `row` and the next one.
+    /// Unlike an empty row, this spacer is not data, and it stays after this row when rows are
+    /// sorted. See `Row::set_blank_after`. Silently skip if the row does not exist
+    pub fn set_blank_after(&mut self, row: usize, blank: bool) {
+        if let Some(r) = self.rows.get_mut(row) {
+            r.set_blank_after(blank);
+        }
     }
 
-    /// Print the table to `out` and returns the number
-    /// of lines printed, or an error
-    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
-        self.as_ref().print(out)
+    /// Remove the row at position `index`. Silently skip if the row does not exist
+    pub fn remove_row(&mut self, index: usize) {
+        if index < self.rows.len() {
+            self.rows.remove(index);
+        }
     }
 
-    /// Print the table to terminal `out`, applying styles when needed and returns the number
-    /// of lines printed, or an error
-    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
-        self.as_ref().print_term(out)
+    /// Get the indices of columns in which every cell, titles excluded, is empty or
+    /// contains only whitespaces. Returns nothing if the table has no rows
+    pub fn empty_columns(&self) -> Vec<usize> {
+        if self.rows.is_empty() {
+            return Vec::new();
+        }
+        (0..self.get_column_num())
+            .filter(|&i| self.rows.iter().all(|r| {
+                r.get_cell_at_column(i).map(|c| c.get_content().trim().is_empty()).unwrap_or(true)
+            }))
+            .collect()
     }
 
-    /// Print the table to standard output. Colors won't be displayed unless
-    /// stdout is a tty terminal, or `force_colorize` is set to `true`.
-    /// In ANSI terminals, colors are displayed using ANSI escape characters. When for example the
-    /// output is redirected to a file, or piped to another program, the output is considered
-    /// as not beeing tty, and ANSI escape characters won't be displayed unless `force colorize`
-    /// is set to `true`.
-    /// # Returns
-    /// A `Result` holding the number of lines printed, or an `io::Error` if any failure happens
-    pub fn print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
-        self.as_ref().print_tty(force_colorize)
+    /// Remove columns returned by `empty_columns`, along with their title
+    /// and their settings
+    pub fn drop_empty_columns(&mut self) {
+        for i in self.empty_columns().into_iter().rev() {
+            self.remove_column_at(i);
+        }
     }
 
-    /// Print the table to standard output. Colors won't be displayed unless
-    /// stdout is a tty terminal. This means that if stdout is redirected to a file, or piped
-    /// to another program, no color will be displayed.
-    /// To force colors rendering, use `print_tty()` method.
-    /// Any failure to print is ignored. For better control, use `print_tty()`.
-    /// Calling `printstd()` is equivalent to calling `print_tty(false)` and ignoring the result.
-    pub fn printstd(&self) {
-        self.as_ref().printstd()
+    /// Keep only the columns for which `pred`, given the index of the column and its title,
+    /// returns `true`. The title is empty if the table has no titles. Other columns are removed
+    /// from the titles and every row, along with their settings, like `drop_empty_columns` does
+    pub fn retain_columns<F: FnMut(usize, &str) -> bool>(&mut self, mut pred: F) {
+        let titles = self.titles.as_ref().as_ref();
+        let colnum = self.get_column_num();
+        let dropped: Vec<usize> = (0..colnum).filter(|&i| {
+            let title = titles.and_then(|t| t.get_cell_at_column(i)).map(Cell::get_content).unwrap_or_default();
+            !pred(i, &title)
+        }).collect();
+        for i in dropped.into_iter().rev() {
+            self.remove_column_at(i);
+        }
     }
 
-    /// Print table in HTML format to `out`.
-    pub fn print_html<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
-        self.as_ref().print_html(out)
+    /// Remove column `i` from the titles and every row, along with its settings
+    fn remove_column_at(&mut self, i: usize) {
+        if let Some(ref mut t) = *self.titles {
+            t.remove_cell_at_column(i);
+        }
+        for r in &mut self.rows {
+            r.remove_cell_at_column(i);
+        }
+        if i < self.column_align.len() {
+            self.column_align.remove(i);
+        }
+        if i < self.column_fixed.len() {
+            self.column_fixed.remove(i);
+        }
+        if i < self.column_empty.len() {
+            self.column_empty.remove(i);
+        }
+        if i < self.column_number.len() {
+            self.column_number.remove(i);
+        }
+        if i < self.column_merge.len() {
+            self.column_merge.remove(i);
+        }
+        if i < self.column_valign.len() {
+            self.column_valign.remove(i);
+        }
+        if i < self.column_sort.len() {
+            self.column_sort.remove(i);
+        }
+        if i < self.column_type.len() {
+            self.column_type.remove(i);
+        }
+        if i < self.column_icon.len() {
+            self.column_icon.remove(i);
+        }
+        if i < self.column_bar.len() {
+            self.column_bar.remove(i);
+        }
+        if i < self.column_anchor.len() {
+            self.column_anchor.remove(i);
+        }
     }
-}
 
-impl Index<usize> for Table {
-    type Output = Row;
-    fn index(&self, idx: usize) -> &Self::Output {
-        &self.rows[idx]
+    /// Show numeric cells of column `col` as a bar proportional to their value against `max`,
+    /// followed by the number itself. Cells which don't contain a number are left unchanged.
+    ///
+    /// Only the display is affected, the content of cells is kept as is. Bars fill the width of
+    /// the column less the widest number: this is the fixed width of the column if set with
+    /// `set_column_fixed`, else the absolute maximum width if any, else the width of its title
+    /// and non numeric cells. Bars are at least 1 column wide
+    pub fn set_column_as_bar(&mut self, col: usize, max: f64) {
+        if col >= self.column_bar.len() {
+            self.column_bar.resize(col + 1, None);
+        }
+        self.column_bar[col] = Some(BarMax(max));
     }
-}
 
-impl<'a> Index<usize> for TableSlice<'a> {
-    type Output = Row;
-    fn index(&self, idx: usize) -> &Self::Output {
-        &self.rows[idx]
+    /// Stop showing column `col` as bars
+    pub fn unset_column_as_bar(&mut self, col: usize) {
+        if let Some(b) = self.column_bar.get_mut(col) {
+            *b = None;
+        }
     }
-}
 
-impl IndexMut<usize> for Table {
-    fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
-        &mut self.rows[idx]
+    /// Get the maximum value of the bars shown in column `col`, if it is shown as bars
+    pub fn get_column_bar_max(&self, col: usize) -> Option<f64> {
+        self.column_bar.get(col).and_then(|b| b.map(|b| b.0))
     }
-}
 
-impl fmt::Display for Table {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        self.as_ref().fmt(fmt)
+    /// Append a row with `"Total"` in column `label_col`, and the sum of numeric cells of each column
+    /// of `sum_cols`. Sums are printed with as many decimals as the most precise value of their column.
+    /// Other columns are left blank, and cells which don't contain a finite number are ignored,
+    /// as are cells spanning several columns. Sums are then displayed with the number format of their
+    /// column, if any.
+    ///
+    /// The footer is an ordinary row: it is sorted, filtered and exported like the other rows,
+    /// and a later call counts it in its sums. Add it once the data is complete.
+    ///
+    /// Return `TableError::ColumnOutOfBounds` if one of the columns is past the number of columns
+    pub fn add_total_footer(&mut self, label_col: usize, sum_cols: &[usize]) -> Result<(), TableError> {
+        let colnum = self.get_column_num();
+        if let Some(&col) = sum_cols.iter().chain(Some(&label_col)).find(|&&c| c >= colnum) {
+            return Err(TableError::ColumnOutOfBounds(col));
+        }
+        let mut footer = Row::new(vec![Cell::default(); colnum]);
+        footer[label_col] = Cell::new("Total");
+        for &col in sum_cols {
+            let mut sum = 0.0;
+            let mut decimals = 0;
+            for c in self.rows.iter().filter_map(|r| r.get_cell_at_column(col)).filter(|c| c.get_hspan() == 1) {
+                let text = c.get_content();
+                let text = text.trim();
+                if let Some(v) = text.parse::<f64>().ok().filter(|v| v.is_finite()) {
+                    sum += v;
+                    // Decimals are counted on the value, as the text may use an exponent
+                    let value = v.to_string();
+                    decimals = decimals.max(value.find('.').map_or(0, |i| value.len() - i - 1));
+                }
+            }
+            footer[col] = Cell::new(&format!("{:.*}", decimals, sum));
+        }
+        self.add_row(footer);
+        Ok(())
     }
-}
 
-impl<'a> fmt::Display for TableSlice<'a> {
-    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        let mut writer = StringWriter::new();
-        if self.print(&mut writer).is_err() {
-            return Err(fmt::Error);
+    /// Set the maximum width of cells so that the rendered table is at most `width` columns wide,
+    /// if possible, using `strategy` to share the available width between columns.
+    /// Cells wrapped by a previous call are unwrapped first. Cells spanning several columns are not wrapped
+    pub fn fit_to_width(&mut self, width: usize, strategy: FitStrategy) {
+        let colnum = self.get_column_num();
+        let mut lines = vec![Vec::new(); colnum];
+        for r in self.titles.iter().chain(self.rows.iter()) {
+            let mut col = 0;
+            for c in r.iter() {
+                if c.get_hspan() == 1 {
+                    lines[col].extend(c.get_content().lines().map(str::to_string));
+                }
+                col += c.get_hspan();
+            }
+        }
+        let natural: Vec<usize> = lines.iter()
+            .map(|l| l.iter().map(|s| display_width(s)).max().unwrap_or(0))
+            .collect();
+        let budget = width.saturating_sub(self.format.get_table_width(&vec![0; colnum]));
+        let widths = match strategy {
+            FitStrategy::Proportional => fit_proportional(&natural, budget),
+            FitStrategy::Balanced => fit_balanced(&lines, budget),
+        };
+        for r in self.titles.iter_mut().chain(self.rows.iter_mut()) {
+            let mut col = 0;
+            for c in r.iter_mut() {
+                if c.get_hspan() == 1 {
+                    c.set_max_width(Some(widths[col]).filter(|&w| w < natural[col]));
+                }
+                col += c.get_hspan();
+            }
         }
-        fmt.write_str(writer.as_string())
     }
-}
 
-impl<B: ToString, A: IntoIterator<Item = B>> FromIterator<A> for Table {
-    fn from_iter<T>(iterator: T) -> Table
-        where T: IntoIterator<Item = A>
-    {
-        Self::init(iterator.into_iter().map(Row::from).collect())
+    /// Set the alignment of cells in column `column` which don't set their own.
+    /// It takes precedence over the format's default alignment
+    pub fn set_column_alignment(&mut self, column: usize, align: Alignment) {
+        if column >= self.column_align.len() {
+            self.column_align.resize(column + 1, None);
+        }
+        self.column_align[column] = Some(align);
     }
-}
 
-impl FromIterator<Row> for Table {
-    fn from_iter<T>(iterator: T) -> Table
-        where T: IntoIterator<Item = Row>
-    {
-        Self::init(iterator.into_iter().collect())
+    /// Remove the alignment set on column `column`
+    pub fn unset_column_alignment(&mut self, column: usize) {
+        if let Some(a) = self.column_align.get_mut(column) {
+            *a = None;
+        }
     }
-}
 
-impl<T, A, B> From<T> for Table
-    where B: ToString,
-          A: IntoIterator<Item = B>,
-          T: IntoIterator<Item = A>
-{
-    fn from(it: T) -> Table {
-        Self::from_iter(it)
+    /// Align data cells of column `column` on the first occurrence of `anchor`, like `:` in
+    /// `key: value` cells or ` ms` after durations, so that it is at the same position in every row.
+    /// Cells which don't contain it are aligned as if it was at their end. Cells setting their
+    /// own alignment are not anchored, and cells setting their own anchor keep it, see
+    /// `Cell::set_anchor`. Titles are not anchored, and anchors only apply to text rendering
+    pub fn set_column_anchor(&mut self, column: usize, anchor: String) {
+        if column >= self.column_anchor.len() {
+            self.column_anchor.resize(column + 1, None);
+        }
+        self.column_anchor[column] = Some(anchor);
     }
-}
 
-impl<'a> IntoIterator for &'a Table {
-    type Item = &'a Row;
-    type IntoIter = Iter<'a, Row>;
-    fn into_iter(self) -> Self::IntoIter {
-        self.as_ref().row_iter()
+    /// Remove the anchor set on column `column`
+    pub fn unset_column_anchor(&mut self, column: usize) {
+        if let Some(a) = self.column_anchor.get_mut(column) {
+            *a = None;
+        }
     }
-}
 
-impl<'a> IntoIterator for &'a mut Table {
-    type Item = &'a mut Row;
-    type IntoIter = IterMut<'a, Row>;
-    fn into_iter(self) -> Self::IntoIter {
-        self.row_iter_mut()
+    /// Get the string cells of column `column` are aligned on, if any
+    pub fn get_column_anchor(&self, column: usize) -> Option<&str> {
+        self.column_anchor.get(column).and_then(|a| a.as_ref().map(String::as_str))
     }
-}
 
-// impl IntoIterator for Table {
-//     type Item = Row;
-//     type IntoIter = std::vec::IntoIter<Self::Item>;
-//     fn into_iter(self) -> Self::IntoIter {
-//         self.rows.into_iter()
-//     }
-// }
+    /// Get the alignment used by cells of column `column` which don't set their own.
+    /// This is the alignment set on the column, else the format's default alignment, else `LEFT`.
+    /// Returns `None` if the column does not exist
+    pub fn get_column_alignment(&self, column: usize) -> Option<Alignment> {
+        self.as_slice().get_column_alignment(column)
+    }
 
-impl <A: Into<Row>> Extend<A> for Table {
-    fn extend<T: IntoIterator<Item=A>>(&mut self, iter: T) {
-        self.rows.extend(iter.into_iter().map(|r| r.into()));
+    /// Force column `column` to be exactly `width` wide. Shorter cells are padded, and longer
+    /// ones are clipped according to their alignment, with `‹` or `›` marking the clipped side.
+    /// Cells spanning several columns are not clipped
+    pub fn set_column_fixed(&mut self, column: usize, width: usize) {
+        if column >= self.column_fixed.len() {
+            self.column_fixed.resize(column + 1, None);
+        }
+        self.column_fixed[column] = Some(width);
     }
-}
 
-/// Iterator over immutable cells in a column
-pub struct ColumnIter<'a>(Iter<'a, Row>, usize);
+    /// Force the width of every column, the first one being `widths[0]` wide, and so on.
+    /// See `set_column_fixed`
+    pub fn set_fixed_widths(&mut self, widths: &[usize]) {
+        for (column, &width) in widths.iter().enumerate() {
+            self.set_column_fixed(column, width);
+        }
+    }
 
-impl<'a> Iterator for ColumnIter<'a> {
-    type Item = &'a Cell;
-    fn next(&mut self) -> Option<&'a Cell> {
-        self.0.next().and_then(|row| row.get_cell(self.1))
+    /// Remove the fixed width set on column `column`
+    pub fn unset_column_fixed(&mut self, column: usize) {
+        if let Some(w) = self.column_fixed.get_mut(column) {
+            *w = None;
+        }
     }
-}
 
-/// Iterator over mutable cells in a column
-pub struct ColumnIterMut<'a>(IterMut<'a, Row>, usize);
+    /// Get the fixed width set on column `column`, if any
+    pub fn get_column_fixed(&self, column: usize) -> Option<usize> {
+        self.column_fixed.get(column).cloned().unwrap_or(None)
+    }
 
-impl<'a> Iterator for ColumnIterMut<'a> {
-    type Item = &'a mut Cell;
-    fn next(&mut self) -> Option<&'a mut Cell> {
-        self.0.next().and_then(|row| row.get_mut_cell(self.1))
+    /// Display `default` in place of empty or missing cells of column `column`, titles excluded.
+    /// The content of the table is not modified, so exports are unaffected
+    pub fn set_column_empty_default(&mut self, column: usize, default: String) {
+        if column >= self.column_empty.len() {
+            self.column_empty.resize(column + 1, None);
+        }
+        self.column_empty[column] = Some(default);
     }
-}
 
-impl<'a> AsRef<TableSlice<'a>> for TableSlice<'a> {
-    fn as_ref(&self) -> &TableSlice<'a> {
-        self
+    /// Remove the default value displayed for empty cells of column `column`
+    pub fn unset_column_empty_default(&mut self, column: usize) {
+        if let Some(d) = self.column_empty.get_mut(column) {
+            *d = None;
+        }
     }
-}
 
-impl<'a> AsRef<TableSlice<'a>> for Table {
-    fn as_ref(&self) -> &TableSlice<'a> {
-        unsafe {
-            // All this is a bit hacky. Let's try to find something else
-            let s = &mut *((self as *const Table) as *mut Table);
-            s.rows.shrink_to_fit();
-            &*(self as *const Table as *const TableSlice<'a>)
+    /// Display numeric cells of column `column`, titles excluded, using `format`.
+    /// The content of the table is not modified, so exports and sums are unaffected
+    pub fn set_column_number_format(&mut self, column: usize, format: NumberFormat) {
+        if column >= self.column_number.len() {
+            self.column_number.resize(column + 1, None);
         }
+        self.column_number[column] = Some(format);
     }
-}
 
-/// Trait implemented by types which can be sliced
-pub trait Slice<'a, E> {
-    /// Type output after slicing
-    type Output: 'a;
-    /// Get a slice from self
-    fn slice(&'a self, arg: E) -> Self::Output;
-}
+    /// Remove the number format set on column `column`
+    pub fn unset_column_number_format(&mut self, column: usize) {
+        if let Some(f) = self.column_number.get_mut(column) {
+            *f = None;
+        }
+    }
 
-impl<'a, T, E> Slice<'a, E> for T
-    where T: AsRef<TableSlice<'a>>,
-          [Row]: Index<E, Output = [Row]>
-{
-    type Output = TableSlice<'a>;
-    fn slice(&'a self, arg: E) -> Self::Output {
-        let sl = self.as_ref();
-        TableSlice {
-            format: sl.format,
-            titles: sl.titles,
-            rows: sl.rows.index(arg),
+    /// Get the number format set on column `column`, if any
+    pub fn get_column_number_format(&self, column: usize) -> Option<NumberFormat> {
+        self.column_number.get(column).cloned().unwrap_or(None)
+    }
+
+    /// Show the sort direction of column `column` by appending `▲` or `▼` to its title when
+    /// printing. The indicator is part of the width of the column. The table is not sorted
+    pub fn set_sortable_indicator(&mut self, column: usize, state: SortState) {
+        if column >= self.column_sort.len() {
+            self.column_sort.resize(column + 1, SortState::None);
         }
+        self.column_sort[column] = state;
     }
-}
 
-/// Create a table filled with some values
-///
-/// All the arguments used for elements must implement the `std::string::ToString` trait
-/// # Syntax
-/// ```text
-/// table!([Element1_ row1, Element2_ row1, ...], [Element1_row2, ...], ...);
-/// ```
-///
-/// # Example
-/// ```
-/// # #[macro_use] extern crate prettytable;
-/// # fn main() {
-/// // Create a table initialized with some rows :
-/// let tab = table!(["Element1", "Element2", "Element3"],
-/// 				 [1, 2, 3],
-/// 				 ["A", "B", "C"]
-/// 				 );
-/// # drop(tab);
-/// # }
-/// ```
-///
-/// Some style can also be given in table creation
-///
-/// ```
-/// # #[macro_use] extern crate prettytable;
-/// # fn main() {
-/// let tab = table!([FrByl->"Element1", Fgc->"Element2", "Element3"],
-/// 				 [FrBy => 1, 2, 3],
-/// 				 ["A", "B", "C"]
-/// 				 );
-/// # drop(tab);
-/// # }
-/// ```
-///
-/// For details about style specifier syntax, check doc for [`Cell::style_spec`](cell/struct.Cell.html#method.style_spec) method
-#[macro_export]
-macro_rules! table {
-    ($([$($content:tt)*]), *) => (
-        $crate::Table::init(vec![$($crate::row![$($content)*]), *])
-    );
-}
+    /// Get the sort direction shown next to the title of column `column`
+    pub fn get_sortable_indicator(&self, column: usize) -> SortState {
+        self.column_sort.get(column).cloned().unwrap_or(SortState::None)
+    }
 
-/// Create a table with `table!` macro, print it to standard output, then return this table for future usage.
-///
-/// The syntax is the same that the one for the `table!` macro
-#[macro_export]
-macro_rules! ptable {
-    ($($content:tt)*) => (
-        {
-            let tab = $crate::table!($($content)*);
-            tab.printstd();
-            tab
+    /// Show `icon` and a space before the title of column `column` when printing, like `⚠ Warnings`.
+    /// The icon is part of the width of the column. Titles are unchanged, so exports don't show it
+    pub fn set_column_icon(&mut self, column: usize, icon: String) {
+        if column >= self.column_icon.len() {
+            self.column_icon.resize(column + 1, None);
         }
-    );
-}
+        self.column_icon[column] = Some(icon);
+    }
 
-#[cfg(test)]
-mod tests {
-    use crate::{Table, Slice, Row, Cell, format};
-    use format::consts::{FORMAT_DEFAULT, FORMAT_NO_LINESEP, FORMAT_NO_COLSEP, FORMAT_CLEAN, FORMAT_BOX_CHARS};
-    use crate::utils::StringWriter;
+    /// Remove the icon shown before the title of column `column`
+    pub fn unset_column_icon(&mut self, column: usize) {
+        if let Some(i) = self.column_icon.get_mut(column) {
+            *i = None;
+        }
+    }
 
-    #[test]
-    fn table() {
-        let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        let out = "\
-+-----+----+-----+
-| t1  | t2 | t3  |
-+=====+====+=====+
-| a   | bc | def |
+    /// Get the icon shown before the title of column `column`, if any
+    pub fn get_column_icon(&self, column: usize) -> Option<&str> {
+        self.column_icon.get(column).and_then(|i| i.as_ref().map(String::as_str))
+    }
+
+    /// Set the type of values held by column `column`, so that `sort_view` compares them
+    /// by magnitude. Printing is not affected
+    pub fn set_column_type(&mut self, column: usize, ty: ColumnType) {
+        if column >= self.column_type.len() {
+            self.column_type.resize(column + 1, None);
+        }
+        self.column_type[column] = Some(ty);
+    }
+
+    /// Remove the type set on column `column`, its cells are then sorted as numbers or text
+    pub fn unset_column_type(&mut self, column: usize) {
+        if column < self.column_type.len() {
+            self.column_type[column] = None;
+        }
+    }
+
+    /// Get the type set on column `column`, if any
+    pub fn get_column_type(&self, column: usize) -> Option<ColumnType> {
+        self.column_type.get(column).cloned().unwrap_or(None)
+    }
+
+    /// Set the vertical alignment of cells of column `column` which are shorter than their row
+    pub fn set_column_valign(&mut self, column: usize, valign: VAlign) {
+        if column >= self.column_valign.len() {
+            self.column_valign.resize(column + 1, VAlign::Top);
+        }
+        self.column_valign[column] = valign;
+    }
+
+    /// Get the vertical alignment of cells of column `column`. Defaults to `VAlign::Top`
+    pub fn get_column_valign(&self, column: usize) -> VAlign {
+        self.column_valign.get(column).cloned().unwrap_or(VAlign::Top)
+    }
+
+    /// Enable or disable vertical merging of column `column`. When enabled, a cell with the
+    /// same content as the cell above it is displayed blank, so that only the first cell of
+    /// a run of identical values is visible. The content of the table is not modified
+    pub fn set_column_merge_vertical(&mut self, column: usize, merge: bool) {
+        if column >= self.column_merge.len() {
+            self.column_merge.resize(column + 1, false);
+        }
+        self.column_merge[column] = merge;
+    }
+
+    /// Check if vertical merging is enabled on column `column`
+    pub fn get_column_merge_vertical(&self, column: usize) -> bool {
+        self.column_merge.get(column).cloned().unwrap_or(false)
+    }
+
+    /// Get the default value displayed for empty cells of column `column`, if any
+    pub fn get_column_empty_default(&self, column: usize) -> Option<&str> {
+        self.column_empty.get(column).and_then(|d| d.as_ref().map(String::as_str))
+    }
+
+    /// Set whether the last column is followed by its right padding when the table has no right
+    /// border, see `TableFormat::pad_last_column`. Defaults to `true`
+    pub fn set_pad_last_column(&mut self, pad: bool) {
+        self.format.pad_last_column(pad);
+    }
+
+    /// Set what separates titles from the body: the title line, which is the default, a blank
+    /// line, or nothing. See `TableFormat::header_separator`
+    pub fn set_header_separator(&mut self, separator: HeaderSeparator) {
+        self.format.header_separator(separator);
+    }
+
+    /// Group columns by `size`, drawing the column separator after every `size`th column with the
+    /// group separator of the format (`‖` by default). Grouping is disabled with 0, the default.
+    /// See `TableFormat::column_group_size`
+    pub fn set_column_group_size(&mut self, size: usize) {
+        self.format.column_group_size(size);
+    }
+
+    /// Set what happens when the table is wider than the maximum width of its format: print it
+    /// as is, split its columns into blocks, or cut it with a `→` column.
+    /// See `TableFormat::horizontal_overflow`
+    pub fn set_horizontal_overflow(&mut self, overflow: HOverflow) {
+        self.format.horizontal_overflow(overflow);
+    }
+
+    /// Enable or disable adaptive colors. When enabled, `print_term` guesses whether the terminal
+    /// background is light or dark from the `COLORFGBG` environment variable, and replaces foreground
+    /// colors which would be hard to read on it, like white text on a light background.
+    /// Cells with their own background color are left unchanged, and colors are printed as is
+    /// if the background can't be detected
+    pub fn set_adaptive_colors(&mut self, adaptive: bool) {
+        self.adaptive_colors = adaptive;
+    }
+
+    /// Check if adaptive colors are enabled
+    pub fn get_adaptive_colors(&self) -> bool {
+        self.adaptive_colors
+    }
+
+    /// Enable or disable flushing the output after each row when printing, disabled by default.
+    /// This makes rows appear as soon as they are printed on slow outputs, like pipes or sockets,
+    /// at the cost of throughput
+    pub fn set_flush_per_row(&mut self, flush: bool) {
+        self.flush_per_row = flush;
+    }
+
+    /// Check if the output is flushed after each row when printing
+    pub fn get_flush_per_row(&self) -> bool {
+        self.flush_per_row
+    }
+
+    /// Print every `n`th row in bold with `print_term`, which are rows `n - 1`, `2n - 1` and so on,
+    /// to help following long rows in tall tables. `0`, the default, disables it.
+    /// Layout is unchanged, and `print` ignores it
+    pub fn set_emphasis_stride(&mut self, n: usize) {
+        self.emphasis_stride = n;
+    }
+
+    /// Get the interval between rows printed in bold, `0` if disabled
+    pub fn get_emphasis_stride(&self) -> usize {
+        self.emphasis_stride
+    }
+
+    /// Choose what `print` and `print_term` do when writing to the output fails midway.
+    /// The default, `OnError::Abort`, returns the error right away, which may leave partial
+    /// output in the buffers of the output. `OnError::FlushAndAbort` flushes it first
+    pub fn set_on_error(&mut self, on_error: OnError) {
+        self.on_error = on_error;
+    }
+
+    /// Get what printing does when writing to the output fails
+    pub fn get_on_error(&self) -> OnError {
+        self.on_error
+    }
+
+    /// Set a hard limit on the width of every column, or remove it with `None`.
+    /// Content of wider cells is truncated when printed. This protects against
+    /// enormous cells when rendering untrusted data
+    pub fn set_absolute_max_width(&mut self, width: Option<usize>) {
+        self.abs_max_width = width;
+    }
+
+    /// Get the hard limit on the width of columns, if any
+    pub fn get_absolute_max_width(&self) -> Option<usize> {
+        self.abs_max_width
+    }
+
+    /// Apply `theme` to the table, replacing its format and column alignments
+    pub fn apply_theme(&mut self, theme: &Theme) {
+        let (format, column_align) = theme.clone().into_parts();
+        *self.format = format;
+        self.column_align = column_align;
+    }
+
+    /// Get the current appearance of the table as a `Theme`
+    pub fn theme(&self) -> Theme {
+        Theme::from_parts(*self.format, self.column_align.clone())
+    }
+
+    /// Consume the table and return its rows. Titles and format are dropped
+    pub fn into_rows(self) -> Vec<Row> {
+        self.rows
+    }
+
+    /// Consume the table and return its titles, if any, and its rows
+    pub fn into_parts(self) -> (Option<Row>, Vec<Row>) {
+        (*self.titles, self.rows)
+    }
+
+    /// Sort rows by the key extracted from each of them by `f`, like `slice::sort_by_key`.
+    /// The sort is stable, and titles are left in place
+    pub fn sort_by_key<K: Ord, F: FnMut(&Row) -> K>(&mut self, f: F) {
+        self.rows.sort_by_key(f);
+    }
+
+    /// Remove consecutive repeated rows
+    pub fn dedup(&mut self) {
+        self.rows.dedup();
+    }
+
+    /// Remove consecutive rows for which `same` returns `true`.
+    ///
+    /// Like `Vec::dedup_by`, `same` is given the row being checked first, then the previous
+    /// row which is kept. Only consecutive matches are removed, so sort the table first to
+    /// remove all duplicates.
+    pub fn dedup_by<F: FnMut(&Row, &Row) -> bool>(&mut self, mut same: F) {
+        self.rows.dedup_by(|a, b| same(a, b));
+    }
+
+    /// Replace each group of consecutive rows having the same content in column `group_col`
+    /// with the single row returned by `agg` for the rows of this group, in order. Sort the
+    /// table on `group_col` first so that each group is made of all its rows. Titles are kept.
+    ///
+    /// Return `TableError::ColumnOutOfBounds` if `group_col` is past the number of columns
+    pub fn collapse_groups<F: Fn(&[&Row]) -> Row>(&mut self, group_col: usize, agg: F) -> Result<(), TableError> {
+        if group_col >= self.get_column_num() {
+            return Err(TableError::ColumnOutOfBounds(group_col));
+        }
+        let key = |r: &Row| r.get_cell_at_column(group_col).map(Cell::get_content).unwrap_or_default();
+        let mut collapsed = Vec::new();
+        let mut group: Vec<&Row> = Vec::new();
+        let mut group_key = None;
+        for r in &self.rows {
+            let k = key(r);
+            if group_key.as_ref().map_or(false, |g| *g != k) {
+                collapsed.push(agg(&group));
+                group.clear();
+            }
+            group_key = Some(k);
+            group.push(r);
+        }
+        if !group.is_empty() {
+            collapsed.push(agg(&group));
+        }
+        self.rows = collapsed;
+        Ok(())
+    }
+
+    /// Return an iterator over the immutable cells of the column specified by `column`
+    pub fn column_iter(&self, column: usize) -> ColumnIter {
+        ColumnIter(self.rows.iter(), column)
+    }
+
+    /// Replace every occurrence of `from` by `to` in all the cells of the table, titles excepted.
+    /// Return the number of replacements made, which is 0 if `from` is empty
+    pub fn replace_in_cells(&mut self, from: &str, to: &str) -> usize {
+        self.rows.iter_mut()
+            .flat_map(|r| r.iter_mut())
+            .map(|c| c.replace(from, to))
+            .sum()
+    }
+
+    /// Replace every occurrence of `from` by `to` in the cells of column `column`, titles excepted.
+    /// Return the number of replacements made, which is 0 if `from` is empty
+    pub fn replace_in_column(&mut self, column: usize, from: &str, to: &str) -> usize {
+        self.rows.iter_mut()
+            .filter_map(|r| r.get_mut_cell(column))
+            .map(|c| c.replace(from, to))
+            .sum()
+    }
+
+    /// Return an iterator over the mutable cells of the column specified by `column`
+    pub fn column_iter_mut(&mut self, column: usize) -> ColumnIterMut {
+        ColumnIterMut(self.rows.iter_mut(), column)
+    }
+
+    /// For each column, get the index of the row whose cell is the widest, and the width of this
+    /// cell, as used to compute the width of the column when printing. The titles are reported as
+    /// row `TITLE_ROW`. The first row wins ties, titles coming first.
+    /// Cells spanning several columns count for a share of their width in each of them.
+    /// See `TableSlice::widest_cells`
+    pub fn widest_cells(&self) -> Vec<Option<(usize, usize)>> {
+        self.as_slice().widest_cells()
+    }
+
+    /// Return an iterator over the mutable cells of column `column`, or `None` if the column
+    /// does not exist. Unlike `column_iter_mut`, rows too short to have a cell in this column
+    /// are skipped instead of ending the iteration. Since the content of a cell cannot be
+    /// modified, edit a column by replacing its cells:
+    ///
+    /// ```
+    /// # #[macro_use] extern crate prettytable;
+    /// # use prettytable::Cell;
+    /// # fn main() {
+    /// let mut table = table!([" a ", "b"], [" c "]);
+    /// for cell in table.get_column_mut(0).unwrap() {
+    ///     *cell = Cell::new(cell.get_content().trim());
+    /// }
+    /// assert_eq!(table[1][0].get_content(), "c");
+    /// # }
+    /// ```
+    pub fn get_column_mut(&mut self, column: usize) -> Option<impl Iterator<Item = &mut Cell>> {
+        if column >= self.get_column_num() {
+            return None;
+        }
+        Some(self.rows.iter_mut().filter_map(move |r| r.get_mut_cell(column)))
+    }
+
+    /// Returns an iterator over immutable rows
+    pub fn row_iter(&self) -> Iter<Row> {
+        self.rows.iter()
+    }
+
+    /// Call `f` with the index of each row and the row itself, stopping at the first error
+    pub fn try_for_each_row<E, F: FnMut(usize, &Row) -> Result<(), E>>(&self, f: F) -> Result<(), E> {
+        self.as_slice().try_for_each_row(f)
+    }
+
+    /// Return the `(row, col)` position of the first cell whose content is `needle`, if any.
+    /// Titles are not searched, and `col` is the index of the cell in its row
+    pub fn find_cell(&self, needle: &str) -> Option<(usize, usize)> {
+        self.as_slice().find_cell(needle)
+    }
+
+    /// Return the `(row, col)` position of the first cell for which `predicate` returns `true`, if any.
+    /// Titles are not searched, and `col` is the index of the cell in its row
+    pub fn find_cell_by<F: FnMut(&Cell) -> bool>(&self, predicate: F) -> Option<(usize, usize)> {
+        self.as_slice().find_cell_by(predicate)
+    }
+
+    /// Return the `(row, col)` positions of all the cells whose content is `needle`, row by row.
+    /// Titles are not searched, and `col` is the index of the cell in its row
+    pub fn find_all_cells(&self, needle: &str) -> Vec<(usize, usize)> {
+        self.as_slice().find_all_cells(needle)
+    }
+
+    /// Returns an iterator over mutable rows
+    pub fn row_iter_mut(&mut self) -> IterMut<Row> {
+        self.rows.iter_mut()
+    }
+
+    /// Print the table to `out` and returns the number
+    /// of lines printed, or an error
+    pub fn print<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.as_slice().print(out)
+    }
+
+    /// Return the indices of rows in the order they would have if sorted on column `col`,
+    /// without modifying the table. See `TableSlice::sort_view`
+    pub fn sort_view(&self, col: usize, ascending: bool) -> Result<Vec<usize>, TableError> {
+        self.as_slice().sort_view(col, ascending)
+    }
+
+    /// Guess the type of the values of each column from its body cells.
+    /// See `TableSlice::infer_column_types`
+    pub fn infer_column_types(&self) -> Vec<ColumnType> {
+        self.as_slice().infer_column_types()
+    }
+
+    /// Compute statistics about the rendering of the table. The table is printed to
+    /// measure its size, so this costs as much as `print`
+    pub fn render_stats(&self) -> RenderStats {
+        self.as_slice().render_stats()
+    }
+
+    /// Estimate the width of each column from the titles and the first `sample` rows only.
+    /// See `TableSlice::estimate_widths`
+    pub fn estimate_widths(&self, sample: usize) -> Vec<usize> {
+        self.as_slice().estimate_widths(sample)
+    }
+
+    /// Print the table to terminal `out`, applying styles when needed and returns the number
+    /// of lines printed, or an error
+    pub fn print_term<T: Terminal + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.as_slice().print_term(out)
+    }
+
+    /// Print the table to standard output. Colors won't be displayed unless
+    /// stdout is a tty terminal, or `force_colorize` is set to `true`.
+    /// In ANSI terminals, colors are displayed using ANSI escape characters. When for example the
+    /// output is redirected to a file, or piped to another program, the output is considered
+    /// as not beeing tty, and ANSI escape characters won't be displayed unless `force colorize`
+    /// is set to `true`.
+    /// # Returns
+    /// A `Result` holding the number of lines printed, or an `io::Error` if any failure happens
+    pub fn print_tty(&self, force_colorize: bool) -> Result<usize, Error> {
+        self.as_slice().print_tty(force_colorize)
+    }
+
+    /// Print the table to standard output. Colors won't be displayed unless
+    /// stdout is a tty terminal. This means that if stdout is redirected to a file, or piped
+    /// to another program, no color will be displayed.
+    /// To force colors rendering, use `print_tty()` method.
+    /// Any failure to print is ignored. For better control, use `print_tty()`.
+    /// Calling `printstd()` is equivalent to calling `print_tty(false)` and ignoring the result.
+    pub fn printstd(&self) {
+        self.as_slice().printstd()
+    }
+
+    /// Print the table to standard error. Colors are displayed only if stderr is a tty terminal.
+    /// Any failure to print is ignored, like with `printstd()`.
+    pub fn printstderr(&self) {
+        self.as_slice().printstderr()
+    }
+
+    /// Print table in HTML format to `out`.
+    /// Only alignment and cell styles are used: separators, padding, indentation and widths
+    /// of the table format only apply to text rendering
+    pub fn print_html<T: Write + ?Sized>(&self, out: &mut T) -> Result<(), Error> {
+        self.as_slice().print_html(out)
+    }
+
+    /// Print the table to `out` as a GitHub Flavored Markdown table, with cells padded to the
+    /// width of their column if `pretty` is `true`. See `TableSlice::print_markdown`
+    pub fn print_markdown<T: Write + ?Sized>(&self, out: &mut T, pretty: bool) -> Result<(), Error> {
+        self.as_slice().print_markdown(out, pretty)
+    }
+
+    /// Return the table as a Graphviz HTML-like label, to use as `label=<...>` in a node
+    /// definition. See `TableSlice::to_graphviz_label`
+    pub fn to_graphviz_label(&self) -> String {
+        self.as_slice().to_graphviz_label()
+    }
+
+    /// Print the table rotated, each row being printed as a block of `title: value` lines,
+    /// with a rule between blocks. Titles are padded so colons are aligned.
+    /// Columns without title are labelled with their index.
+    /// Returns the number of lines printed, or an error
+    pub fn print_rotated<T: Write + ?Sized>(&self, out: &mut T) -> Result<usize, Error> {
+        self.as_slice().print_rotated(out)
+    }
+
+    /// Print each row to `out` by substituting its cells into `template`, followed by a newline.
+    /// See `TableSlice::print_templated`
+    pub fn print_templated<W: Write + ?Sized>(&self, out: &mut W, template: &str) -> Result<(), Error> {
+        self.as_slice().print_templated(out, template)
+    }
+
+    /// Print rows from `start_row` onward to `out`, with columns of width `widths`, without titles
+    /// nor top and bottom lines. Each row but the first of the table is preceded by an internal line.
+    /// This allows appending rows to the output of a previous rendering which had no bottom line,
+    /// given the widths it used. Cells wider than their column are truncated
+    pub fn append_rows_to_writer<W: Write + ?Sized>(&self, out: &mut W, start_row: usize, widths: &[usize])
+                                                    -> Result<(), Error> {
+        self.as_slice().append_rows_to_writer(out, start_row, widths)
+    }
+
+    /// Print only the header block to `out`, with columns of width `widths` : the top line,
+    /// then the titles followed by the title line if the table has titles.
+    /// Together with `append_rows_to_writer`, this allows printing a table incrementally.
+    /// Cells wider than their column are truncated
+    pub fn print_header<W: Write + ?Sized>(&self, out: &mut W, widths: &[usize]) -> Result<(), Error> {
+        self.as_slice().print_header(out, widths)
+    }
+
+    /// Print `rows`, which don't need to belong to the table, to `out` as a table with columns
+    /// of width `widths`, using the format and column settings of this table.
+    /// See `TableSlice::render_rows`
+    pub fn render_rows<'b, W, I>(&self, out: &mut W, rows: I, widths: &[usize], titles: bool) -> Result<(), Error>
+        where W: Write + ?Sized,
+              I: IntoIterator<Item = &'b Row>
+    {
+        self.as_slice().render_rows(out, rows, widths, titles)
+    }
+
+    /// Print the table to `out`, replacing each rendered line with what `hook` returns for it
+    /// and its index. See `TableSlice::print_with_line_hook`
+    pub fn print_with_line_hook<W, F>(&self, out: &mut W, hook: F) -> Result<(), Error>
+        where W: Write + ?Sized,
+              F: FnMut(usize, &str) -> String
+    {
+        self.as_slice().print_with_line_hook(out, hook)
+    }
+
+    /// Print the table to `out`, with every occurrence of `needle` in cells surrounded
+    /// by `>>` and `<<` markers. Markers are part of the width of cells
+    pub fn print_highlighted<W: Write + ?Sized>(&self, out: &mut W, needle: &str) -> Result<(), Error> {
+        self.as_slice().print_highlighted(out, needle)
+    }
+
+    /// Print the table to terminal `out`, with every occurrence of `needle` in cells displayed
+    /// in reverse video using ANSI escape codes. The width of cells is unaffected
+    pub fn print_term_highlighted<T: Terminal + ?Sized>(&self, out: &mut T, needle: &str) -> Result<(), Error> {
+        self.as_slice().print_term_highlighted(out, needle)
+    }
+
+    /// Print several tables sharing the same titles as a single table to `out`.
+    /// Titles are printed once, followed by the rows of each table, with an internal line
+    /// between tables. Columns are as wide as needed by all the tables, and the format of
+    /// the first table is used for titles and outer lines.
+    /// Fails with `ErrorKind::InvalidInput` if tables don't have the same titles and number of columns
+    pub fn print_many<W: Write>(tables: &[&Table], out: &mut W) -> Result<(), Error> {
+        if tables.is_empty() {
+            return Ok(());
+        }
+        let colnum = tables[0].get_column_num();
+        if tables.iter().any(|t| t.titles != tables[0].titles || t.get_column_num() != colnum) {
+            return Err(Error::new(io::ErrorKind::InvalidInput,
+                                  "Tables don't have the same titles and number of columns"));
+        }
+        let displayed: Vec<(Option<Row>, Vec<Row>)> = tables.iter().map(|t| t.as_slice().displayed_rows()).collect();
+        let slices: Vec<TableSlice> = displayed.iter().zip(tables).map(|((titles, rows), t)| TableSlice {
+            titles,
+            rows,
+            ..t.as_slice()
+        }).collect();
+        let mut col_width = vec![0usize; colnum];
+        for t in &slices {
+            for (w, tw) in col_width.iter_mut().zip(t.get_all_column_width()) {
+                *w = tw.max(*w);
+            }
+        }
+        let first = &slices[0];
+        let format = first.format;
+        format.print_line_separator(out, &col_width, LinePosition::Top)?;
+        if let Some(ref t) = *first.titles {
+            let col_align: Vec<Alignment> = (0..colnum).map(|i| first.get_effective_alignment(i)).collect();
+            let blank;
+            let t = if first.titles_visible {
+                t
+            } else {
+                blank = t.blanked();
+                &blank
+            };
+            t.print(out, format, &col_width, &col_align, &[], first.column_valign)?;
+            format.print_header_separator(out, &col_width, t)?;
+        }
+        let mut iter = slices.iter().filter(|t| !t.rows.is_empty()).peekable();
+        let mut last = None;
+        while let Some(t) = iter.next() {
+            t.__print_rows(out, &col_width, &Row::print)?;
+            last = t.rows.last();
+            if let (Some(r), Some(_)) = (last, iter.peek()) {
+                format.print_line_separator_below(out, &col_width, LinePosition::Intern, r)?;
+            }
+        }
+        match last {
+            Some(r) => format.print_line_separator_below(out, &col_width, LinePosition::Bottom, r)?,
+            None => format.print_line_separator(out, &col_width, LinePosition::Bottom)?,
+        };
+        out.flush()
+    }
+}
+
+impl Index<usize> for Table {
+    type Output = Row;
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.rows[idx]
+    }
+}
+
+impl<'a> Index<usize> for TableSlice<'a> {
+    type Output = Row;
+    fn index(&self, idx: usize) -> &Self::Output {
+        &self.rows[idx]
+    }
+}
+
+impl IndexMut<usize> for Table {
+    fn index_mut(&mut self, idx: usize) -> &mut Self::Output {
+        &mut self.rows[idx]
+    }
+}
+
+impl fmt::Display for Table {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.as_slice().fmt(fmt)
+    }
+}
+
+/// The width, fill and alignment flags of the formatter are applied to the table as a block,
+/// eg `format!("{:^100}", table)` centers every line of the table in 100 columns
+impl<'a> fmt::Display for TableSlice<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        let mut writer = StringWriter::new();
+        if self.print(&mut writer).is_err() {
+            return Err(fmt::Error);
+        }
+        let table = writer.as_string();
+        let width = match fmt.width() {
+            Some(w) => w,
+            None => return fmt.write_str(table),
+        };
+        let block = table.lines().map(display_width).max().unwrap_or(0);
+        let nfill = width.saturating_sub(block);
+        let left = match fmt.align() {
+            Some(fmt::Alignment::Right) => nfill,
+            Some(fmt::Alignment::Center) => nfill / 2,
+            _ => 0,
+        };
+        let fill = fmt.fill();
+        let mut lines = table.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            let last = lines.peek().is_none();
+            if last && line.is_empty() {
+                break;
+            }
+            let content = line.trim_end_matches('\r');
+            let right = nfill - left + block - display_width(content);
+            for _ in 0..left {
+                fmt.write_char(fill)?;
+            }
+            fmt.write_str(content)?;
+            for _ in 0..right {
+                fmt.write_char(fill)?;
+            }
+            fmt.write_str(&line[content.len()..])?;
+            if !last {
+                fmt.write_char('\n')?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for Table {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.as_slice().debug_fmt("Table", fmt)
+    }
+}
+
+impl<'a> fmt::Debug for TableSlice<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        self.debug_fmt("TableSlice", fmt)
+    }
+}
+
+impl<'a> TableSlice<'a> {
+    /// Write the rendered table, followed by the raw fields when
+    /// the alternate flag is set
+    fn debug_fmt(&self, name: &str, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        writeln!(fmt, "{}", name)?;
+        fmt::Display::fmt(self, fmt)?;
+        if fmt.alternate() {
+            fmt.debug_struct(name)
+                .field("format", self.format)
+                .field("titles", self.titles)
+                .field("rows", &self.rows)
+                .field("column_align", &self.column_align)
+                .field("column_fixed", &self.column_fixed)
+                .field("column_empty", &self.column_empty)
+                .field("column_number", &self.column_number)
+                .field("column_merge", &self.column_merge)
+                .field("column_valign", &self.column_valign)
+                .field("column_sort", &self.column_sort)
+                .field("column_type", &self.column_type)
+                .field("column_icon", &self.column_icon)
+                .field("column_bar", &self.column_bar)
+                .field("column_anchor", &self.column_anchor)
+                .field("abs_max_width", &self.abs_max_width)
+                .field("adaptive_colors", &self.adaptive_colors)
+                .field("flush_per_row", &self.flush_per_row)
+                .field("emphasis_stride", &self.emphasis_stride)
+                .field("on_error", &self.on_error)
+                .field("titles_visible", &self.titles_visible)
+                .field("schema_band", &self.schema_band)
+                .finish()?;
+        }
+        Ok(())
+    }
+}
+
+impl<B: ToString, A: IntoIterator<Item = B>> FromIterator<A> for Table {
+    fn from_iter<T>(iterator: T) -> Table
+        where T: IntoIterator<Item = A>
+    {
+        Self::init(iterator.into_iter().map(Row::from).collect())
+    }
+}
+
+impl FromIterator<Row> for Table {
+    fn from_iter<T>(iterator: T) -> Table
+        where T: IntoIterator<Item = Row>
+    {
+        Self::init(iterator.into_iter().collect())
+    }
+}
+
+impl<T, A, B> From<T> for Table
+    where B: ToString,
+          A: IntoIterator<Item = B>,
+          T: IntoIterator<Item = A>
+{
+    fn from(it: T) -> Table {
+        Self::from_iter(it)
+    }
+}
+
+impl<'a> IntoIterator for &'a Table {
+    type Item = &'a Row;
+    type IntoIter = Iter<'a, Row>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a mut Table {
+    type Item = &'a mut Row;
+    type IntoIter = IterMut<'a, Row>;
+    fn into_iter(self) -> Self::IntoIter {
+        self.row_iter_mut()
+    }
+}
+
+// impl IntoIterator for Table {
+//     type Item = Row;
+//     type IntoIter = std::vec::IntoIter<Self::Item>;
+//     fn into_iter(self) -> Self::IntoIter {
+//         self.rows.into_iter()
+//     }
+// }
+
+impl <A: Into<Row>> Extend<A> for Table {
+    fn extend<T: IntoIterator<Item=A>>(&mut self, iter: T) {
+        self.rows.extend(iter.into_iter().map(|r| r.into()));
+    }
+}
+
+/// Iterator over immutable cells in a column
+pub struct ColumnIter<'a>(Iter<'a, Row>, usize);
+
+impl<'a> Iterator for ColumnIter<'a> {
+    type Item = &'a Cell;
+    fn next(&mut self) -> Option<&'a Cell> {
+        self.0.next().and_then(|row| row.get_cell(self.1))
+    }
+}
+
+/// Iterator over mutable cells in a column
+pub struct ColumnIterMut<'a>(IterMut<'a, Row>, usize);
+
+impl<'a> Iterator for ColumnIterMut<'a> {
+    type Item = &'a mut Cell;
+    fn next(&mut self) -> Option<&'a mut Cell> {
+        self.0.next().and_then(|row| row.get_mut_cell(self.1))
+    }
+}
+
+impl<'a> AsRef<TableSlice<'a>> for TableSlice<'a> {
+    fn as_ref(&self) -> &TableSlice<'a> {
+        self
+    }
+}
+
+impl<'a> From<&'a Table> for TableSlice<'a> {
+    fn from(table: &'a Table) -> TableSlice<'a> {
+        table.as_slice()
+    }
+}
+
+impl<'a, 'b: 'a> From<&'a TableSlice<'b>> for TableSlice<'a> {
+    fn from(slice: &'a TableSlice<'b>) -> TableSlice<'a> {
+        slice.clone()
+    }
+}
+
+/// Trait implemented by types which can be sliced
+pub trait Slice<'a, E> {
+    /// Type output after slicing
+    type Output: 'a;
+    /// Get a slice from self
+    fn slice(&'a self, arg: E) -> Self::Output;
+}
+
+impl<'a, T, E> Slice<'a, E> for T
+    where T: 'a,
+          &'a T: Into<TableSlice<'a>>,
+          [Row]: Index<E, Output = [Row]>
+{
+    type Output = TableSlice<'a>;
+    fn slice(&'a self, arg: E) -> Self::Output {
+        let sl: TableSlice<'a> = self.into();
+        TableSlice {
+            format: sl.format,
+            titles: sl.titles,
+            rows: sl.rows.index(arg),
+            column_align: sl.column_align,
+            column_fixed: sl.column_fixed,
+            column_empty: sl.column_empty,
+            column_number: sl.column_number,
+            column_merge: sl.column_merge,
+            column_valign: sl.column_valign,
+            column_sort: sl.column_sort,
+            column_type: sl.column_type,
+            column_icon: sl.column_icon,
+            column_bar: sl.column_bar,
+            column_anchor: sl.column_anchor,
+            abs_max_width: sl.abs_max_width,
+            adaptive_colors: sl.adaptive_colors,
+            flush_per_row: sl.flush_per_row,
+            emphasis_stride: sl.emphasis_stride,
+            on_error: sl.on_error,
+            titles_visible: sl.titles_visible,
+            schema_band: sl.schema_band,
+        }
+    }
+}
+
+/// Create a table filled with some values
+///
+/// All the arguments used for elements must implement the `std::string::ToString` trait
+/// # Syntax
+/// ```text
+/// table!([Element1_ row1, Element2_ row1, ...], [Element1_row2, ...], ...);
+/// ```
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// // Create a table initialized with some rows :
+/// let tab = table!(["Element1", "Element2", "Element3"],
+/// 				 [1, 2, 3],
+/// 				 ["A", "B", "C"]
+/// 				 );
+/// # drop(tab);
+/// # }
+/// ```
+///
+/// Some style can also be given in table creation
+///
+/// ```
+/// # #[macro_use] extern crate prettytable;
+/// # fn main() {
+/// let tab = table!([FrByl->"Element1", Fgc->"Element2", "Element3"],
+/// 				 [FrBy => 1, 2, 3],
+/// 				 ["A", "B", "C"]
+/// 				 );
+/// # drop(tab);
+/// # }
+/// ```
+///
+/// For details about style specifier syntax, check doc for [`Cell::style_spec`](cell/struct.Cell.html#method.style_spec) method
+#[macro_export]
+macro_rules! table {
+    ($([$($content:tt)*]), *) => (
+        $crate::Table::init(vec![$($crate::row![$($content)*]), *])
+    );
+}
+
+/// Create a table with `table!` macro, print it to standard output, then return this table for future usage.
+///
+/// The syntax is the same that the one for the `table!` macro
+#[macro_export]
+macro_rules! ptable {
+    ($($content:tt)*) => (
+        {
+            let tab = $crate::table!($($content)*);
+            tab.printstd();
+            tab
+        }
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Table, TableSlice, Slice, Row, Cell, TableError, OnError, format};
+    use format::Alignment;
+    use format::consts::{FORMAT_DEFAULT, FORMAT_NO_LINESEP, FORMAT_NO_COLSEP, FORMAT_CLEAN, FORMAT_BOX_CHARS};
+    use crate::utils::{StringWriter, Background, display_width};
+    use crate::{Attr, Terminal};
+    use std::collections::HashMap;
+    use term::terminfo::{TermInfo, TerminfoTerminal};
+
+    #[test]
+    fn table() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        let out = "\
++-----+----+-----+
+| t1  | t2 | t3  |
++=====+====+=====+
+| a   | bc | def |
++-----+----+-----+
+| def | bc | a   |
++-----+----+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+        table.unset_titles();
+        let out = "\
++-----+----+-----+
+| a   | bc | def |
 +-----+----+-----+
 | def | bc | a   |
 +-----+----+-----+
 ";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(5, table.print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn index() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
++-----+--------+-----+
+| t1  | t2     | t3  |
++=====+========+=====+
+| a   | bc     | def |
++-----+--------+-----+
+| def | newval | a   |
++-----+--------+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn table_size() {
+        let mut table = Table::new();
+        assert!(table.is_empty());
+        assert!(table.as_slice().is_empty());
+        assert_eq!(table.len(), 0);
+        assert_eq!(table.as_slice().len(), 0);
+        assert_eq!(table.get_column_num(), 0);
+        assert_eq!(table.as_slice().get_column_num(), 0);
+        table.add_empty_row();
+        assert!(!table.is_empty());
+        assert!(!table.as_slice().is_empty());
+        assert_eq!(table.len(), 1);
+        assert_eq!(table.as_slice().len(), 1);
+        assert_eq!(table.get_column_num(), 0);
+        assert_eq!(table.as_slice().get_column_num(), 0);
+        table[0].add_cell(Cell::default());
+        assert_eq!(table.get_column_num(), 1);
+        assert_eq!(table.as_slice().get_column_num(), 1);
+    }
+
+    #[test]
+    fn get_row() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        assert!(table.get_row(12).is_none());
+        assert!(table.get_row(1).is_some());
+        assert_eq!(table.get_row(1).unwrap()[0].get_content(), "def");
+        assert!(table.get_mut_row(12).is_none());
+        assert!(table.get_mut_row(1).is_some());
+        table.get_mut_row(1).unwrap().add_cell(Cell::new("z"));
+        assert_eq!(table.get_row(1).unwrap()[3].get_content(), "z");
+    }
+
+    #[test]
+    fn add_row_interned() {
+        let mut table = Table::new();
+        table.add_row_interned(vec!["a", "OK"]);
+        table.add_row_interned(vec!["b".to_string(), "OK".to_string()]);
+        assert_eq!(table, table!(["a", "OK"], ["b", "OK"]));
+    }
+
+    #[test]
+    fn try_for_each_row() {
+        let table = table!(["a"], ["b"], ["c"]);
+        let mut seen = Vec::new();
+        let res = table.try_for_each_row(|i, r| {
+            seen.push(i);
+            if r[0].get_content() == "b" { Err(i) } else { Ok(()) }
+        });
+        assert_eq!(res, Err(1));
+        assert_eq!(seen, vec![0, 1]);
+        assert_eq!(table.slice(1..).try_for_each_row(|_, _| Ok::<(), ()>(())), Ok(()));
+    }
+
+    #[test]
+    fn can_add_row() {
+        let mut table = Table::new();
+        let row = Row::from(vec!["a", "b", "c"]);
+        assert_eq!(table.can_add_row(&row), Ok(()));
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        assert_eq!(table.can_add_row(&row), Err(TableError::ColumnOutOfBounds(2)));
+        assert!(table.try_add_row(row).is_err());
+        assert!(table.is_empty());
+        assert!(table.try_add_row(Row::from(vec!["a"])).is_ok());
+        assert_eq!(table.len(), 1);
+    }
+
+    #[test]
+    fn auto_grow_columns() {
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        table.add_row(Row::from(vec!["a"]));
+        assert!(!table.get_auto_grow_columns());
+        table.set_auto_grow_columns(true);
+        assert_eq!(table.can_add_row(&Row::from(vec!["b", "c", "d", "e"])), Ok(()));
+        assert!(table.try_add_row(Row::from(vec!["b", "c", "d", "e"])).is_ok());
+        let titles: Vec<String> = table.titles.as_ref().as_ref().unwrap().iter().map(Cell::get_content).collect();
+        assert_eq!(titles, vec!["t1", "t2", "Column 3", "Column 4"]);
+        assert_eq!(table[0].len(), 4);
+        assert_eq!(table[0][3].get_content(), "");
+        assert!(table.try_add_row(Row::from(vec!["f"])).is_ok());
+        assert_eq!(table.titles.as_ref().as_ref().unwrap().len(), 4);
+    }
+
+    #[test]
+    fn graphviz_label() {
+        let mut table = table!(["a<b", "1"], ["two\nlines", "22"], [H2->"span"]);
+        table.set_titles(Row::from(vec!["name", ""]));
+        table.set_column_alignment(1, Alignment::RIGHT);
+        assert_eq!(table.to_graphviz_label(),
+                   "<TABLE BORDER=\"0\" CELLBORDER=\"1\" CELLSPACING=\"0\">\
+                    <TR><TD ALIGN=\"LEFT\"><B>name</B></TD><TD ALIGN=\"RIGHT\"></TD></TR>\
+                    <TR><TD ALIGN=\"LEFT\">a&lt;b</TD><TD ALIGN=\"RIGHT\">1</TD></TR>\
+                    <TR><TD ALIGN=\"LEFT\">two<BR ALIGN=\"LEFT\"/>lines</TD><TD ALIGN=\"RIGHT\">22</TD></TR>\
+                    <TR><TD ALIGN=\"LEFT\" COLSPAN=\"2\">span</TD></TR>\
+                    </TABLE>");
+    }
+
+    #[test]
+    fn pad_to_rows() {
+        let mut table = table!(["a", "b"]);
+        table.set_titles(Row::from(vec!["t1", "t2", "t3"]));
+        table.pad_to_rows(3, "~");
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[2], Row::from(vec!["~", "~", "~"]));
+        table.pad_to_rows(2, "x");
+        assert_eq!(table.len(), 3);
+        let mut table = Table::new();
+        table.pad_to_rows(1, "");
+        assert_eq!(table[0].len(), 0);
+    }
+
+    #[test]
+    fn add_empty_row() {
+        let mut table = Table::new();
+        assert_eq!(table.len(), 0);
+        table.add_empty_row();
+        assert_eq!(table.len(), 1);
+        assert_eq!(table[0].len(), 0);
+    }
+
+    #[test]
+    fn remove_row() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.remove_row(12);
+        assert_eq!(table.len(), 2);
+        table.remove_row(0);
+        assert_eq!(table.len(), 1);
+        assert_eq!(table[0][0].get_content(), "def");
+    }
+
+    #[test]
+    fn insert_row() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.insert_row(12,
+                         Row::new(vec![Cell::new("1"), Cell::new("2"), Cell::new("3")]));
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[2][1].get_content(), "2");
+        table.insert_row(1,
+                         Row::new(vec![Cell::new("3"), Cell::new("4"), Cell::new("5")]));
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[1][1].get_content(), "4");
+        assert_eq!(table[2][1].get_content(), "bc");
+    }
+
+    #[test]
+    fn into_rows() {
+        let mut table = table!(["a", "bc"], ["def", "g"]);
+        assert_eq!(table.clone().into_rows(), vec![Row::from(vec!["a", "bc"]), Row::from(vec!["def", "g"])]);
+        assert_eq!(table.clone().into_parts().0, None);
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        let (titles, rows) = table.into_parts();
+        assert_eq!(titles, Some(Row::from(vec!["t1", "t2"])));
+        assert_eq!(rows.len(), 2);
+    }
+
+    #[test]
+    fn sort_by_key() {
+        let mut table = table!(["b", "10"], ["a", "9"], ["c", "10"]);
+        table.set_titles(Row::from(vec!["name", "n"]));
+        table.sort_by_key(|r| r[1].get_content().parse::<u32>().ok());
+        assert_eq!(table[0][0].get_content(), "a");
+        assert_eq!(table[1][0].get_content(), "b");
+        assert_eq!(table[2][0].get_content(), "c");
+        table.sort_by_key(|r| std::cmp::Reverse(r[0].get_content()));
+        assert_eq!(table[0][0].get_content(), "c");
+        assert_eq!(table.titles.as_ref().as_ref().unwrap()[0].get_content(), "name");
+    }
+
+    #[test]
+    fn replace_in_cells() {
+        let mut table = table!(["token abc", "abcabc"], ["x", "abc"]);
+        table.set_titles(Row::from(vec!["abc", "y"]));
+        table[0][0].style(Attr::Bold);
+        assert_eq!(table.replace_in_column(1, "abc", "***"), 3);
+        assert_eq!(table[0][1].get_content(), "******");
+        assert_eq!(table.replace_in_cells("abc", "<redacted>"), 1);
+        assert_eq!(table[0][0].get_content(), "token <redacted>");
+        assert_eq!(table[0][0], Cell::new("token <redacted>").with_style(Attr::Bold));
+        assert_eq!(table.titles.as_ref().as_ref().unwrap()[0].get_content(), "abc");
+        assert_eq!(table.replace_in_cells("", "x"), 0);
+        assert_eq!(table.replace_in_column(5, "x", "y"), 0);
+    }
+
+    #[test]
+    fn find_cell() {
+        let mut table = table!(["a", "b"], ["c", "a", "a"]);
+        table.set_titles(Row::from(vec!["x", "y"]));
+        assert_eq!(table.find_cell("a"), Some((0, 0)));
+        assert_eq!(table.find_cell("x"), None);
+        assert_eq!(table.find_all_cells("a"), vec![(0, 0), (1, 1), (1, 2)]);
+        assert!(table.find_all_cells("z").is_empty());
+        assert_eq!(table.find_cell_by(|c| c.get_content().as_str() > "b"), Some((1, 0)));
+        assert_eq!(table.slice(1..).find_cell("a"), Some((0, 1)));
+    }
+
+    #[test]
+    fn print_templated() {
+        let mut table = table!(["Alice", "31"], ["Bob"]);
+        table.set_titles(Row::from(vec!["Name", "Age"]));
+        let mut out = StringWriter::new();
+        table.print_templated(&mut out, "{Name} is {Age} years old {{ok}}").unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "Alice is 31 years old {ok}\nBob is  years old {ok}\n");
+
+        let mut out = StringWriter::new();
+        let err = table.print_templated(&mut out, "{Name} lives in {City}").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+        assert!(err.to_string().contains("\"City\""));
+        assert_eq!(out.as_string(), "");
+        let err = table.print_templated(&mut out, "{Name").unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn sort_view() {
+        let table = table!(["b", "10"], ["a", "9"], ["c", "n/a"], ["d", "10"]);
+        assert_eq!(table.sort_view(1, true), Ok(vec![1, 0, 3, 2]));
+        assert_eq!(table.sort_view(1, false), Ok(vec![2, 0, 3, 1]));
+        assert_eq!(table.sort_view(0, false), Ok(vec![3, 2, 0, 1]));
+        assert_eq!(table.sort_view(2, true), Err(TableError::ColumnOutOfBounds(2)));
+        assert_eq!(table[0][0].get_content(), "b");
+
+        let order = table.sort_view(0, true).unwrap();
+        let mut out = StringWriter::new();
+        table.render_rows(&mut out, order.iter().map(|&i| &table[i]), &[1, 3], false).unwrap();
+        let expected = "\
++---+-----+
+| a | 9   |
++---+-----+
+| b | 10  |
++---+-----+
+| c | n/a |
++---+-----+
+| d | 10  |
++---+-----+
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+    }
+
+    #[test]
+    fn column_type_sort() {
+        let mut table = table!(["1 GB", "2h30m"], ["500 MB", "45s"], ["?", "1d"], ["2KiB", "n/a"], ["1.5 GB", "90m"]);
+        assert_eq!(table.sort_view(0, true), Ok(vec![0, 4, 3, 1, 2]));
+        table.set_column_type(0, format::ColumnType::Bytes);
+        table.set_column_type(1, format::ColumnType::Duration);
+        assert_eq!(table.get_column_type(1), Some(format::ColumnType::Duration));
+        assert_eq!(table.get_column_type(2), None);
+        assert_eq!(table.sort_view(0, true), Ok(vec![3, 1, 0, 4, 2]));
+        assert_eq!(table.sort_view(0, false), Ok(vec![4, 0, 1, 3, 2]));
+        assert_eq!(table.sort_view(1, true), Ok(vec![1, 4, 0, 2, 3]));
+        assert_eq!(table.sort_view(1, false), Ok(vec![2, 0, 4, 1, 3]));
+        table.unset_column_type(0);
+        assert_eq!(table.sort_view(0, true), Ok(vec![0, 4, 3, 1, 2]));
+    }
+
+    #[test]
+    fn estimate_widths() {
+        let mut table = table!(["a", "bb"], ["ccc", "d"], ["a very long cell", "e", "f"]);
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        assert_eq!(table.estimate_widths(2), vec![3, 2]);
+        assert_eq!(table.estimate_widths(0), vec![2, 2]);
+        table.set_titles_visible(false);
+        assert!(table.estimate_widths(0).is_empty());
+        table.set_titles_visible(true);
+        assert_eq!(table.estimate_widths(10), vec![16, 2, 1]);
+
+        let widths = table.estimate_widths(1);
+        table.set_fixed_widths(&widths);
+        assert_eq!(table.estimate_widths(10), vec![2, 2, 1]);
+        let expected = "\
++----+----+---+
+| t1 | t2 |   |
++====+====+===+
+| a  | bb |   |
++----+----+---+
+| c› | d  |   |
++----+----+---+
+| a› | e  | f |
++----+----+---+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), expected);
+
+        let mut table = table!(["a", "1234567"]);
+        table.set_titles(Row::from(vec!["Name", "Size"]));
+        table.set_column_number_format(1, format::NumberFormat::new().compact(1));
+        table.set_sortable_indicator(1, format::SortState::Asc);
+        table.set_column_icon(0, "#".to_string());
+        assert_eq!(table.estimate_widths(100), vec![6, 6]);
+        assert!(table.to_string().starts_with("+--------+--------+"));
+    }
+
+    #[test]
+    fn infer_column_types() {
+        use crate::format::ColumnType::*;
+        let mut table = table!(["1", "1.5", "True", "2024-02-29", "x", "", "1 GB"],
+                               ["-20", "2", "false", "1999-12-31", "2", " ", "2 GB"],
+                               ["", "1e3", "", "", "", "", ""]);
+        table.set_titles(Row::from(vec!["int", "float", "bool", "date", "text", "empty", "bytes", "none"]));
+        assert_eq!(table.infer_column_types(), vec![Integer, Float, Bool, Date, String, String, String, String]);
+        table.add_row(Row::from(vec!["1.0", "x"]));
+        assert_eq!(table.infer_column_types()[..2], [Float, String]);
+        assert!(Table::new().infer_column_types().is_empty());
+
+        let mut table = table!(["1", "true"], ["10", "false"]);
+        table.set_titles(Row::from(vec!["n", "flag"]));
+        table.set_schema_band(true);
+        assert!(table.get_schema_band());
+        let expected = "\
++-----------+--------+
+| n         | flag   |
+| (integer) | (bool) |
++===========+========+
+| 1         | true   |
++-----------+--------+
+| 10        | false  |
++-----------+--------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), expected);
+        table.set_column_type(1, Bool);
+        assert_eq!(table.sort_view(1, true), Ok(vec![1, 0]));
+    }
+
+    #[test]
+    fn column_icon() {
+        let mut table = table!(["3", "ok"]);
+        table.set_titles(Row::from(vec!["Warnings", "Status"]));
+        table.set_column_icon(0, "⚠".to_string());
+        table.set_sortable_indicator(0, format::SortState::Desc);
+        assert_eq!(table.get_column_icon(0), Some("⚠"));
+        assert_eq!(table.get_column_icon(1), None);
+        let expected = "\
++--------------+--------+
+| ⚠ Warnings ▼ | Status |
++==============+========+
+| 3            | ok     |
++--------------+--------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), expected);
+        let mut html = StringWriter::new();
+        table.print_html(&mut html).unwrap();
+        assert!(html.as_string().contains(">Warnings</td>"));
+
+        table.insert_column(0, "#", "").unwrap();
+        assert_eq!(table.get_column_icon(1), Some("⚠"));
+        table.unset_column_icon(1);
+        table.set_sortable_indicator(1, format::SortState::None);
+        assert!(table.to_string().contains("| # | Warnings | Status |"));
+    }
+
+    #[test]
+    fn render_stats() {
+        let mut table = table!(["a", "multi\nline"], ["a long cell", "b"]);
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        table.get_mut_row(1).unwrap()[0].set_max_width(Some(6));
+        let stats = table.render_stats();
+        let out = table.to_string();
+        assert_eq!(stats.rows, 2);
+        assert_eq!(stats.columns, 2);
+        assert_eq!(stats.bytes, out.len());
+        assert_eq!(stats.multiline_cells, 2);
+        assert_eq!(stats.wrapped_cells, 1);
+        assert_eq!(stats.max_cell_width, 6);
+        assert_eq!(stats.width, out.lines().map(|l| l.chars().count()).max().unwrap());
+        assert_eq!(stats.height, out.lines().count());
+        assert_eq!(Table::new().render_stats().height, 2);
+    }
+
+    #[test]
+    fn collapse_groups() {
+        let mut table = table!(["web", "2"], ["web", "3"], ["db", "5"], ["web", "1"]);
+        table.set_titles(Row::from(vec!["service", "errors"]));
+        let sum = |rows: &[&Row]| {
+            let total: i64 = rows.iter().map(|r| r[1].get_content().parse::<i64>().unwrap()).sum();
+            Row::from(vec![rows[0][0].get_content(), total.to_string()])
+        };
+        table.collapse_groups(0, sum).unwrap();
+        assert_eq!(table, {
+            let mut t = table!(["web", "5"], ["db", "5"], ["web", "1"]);
+            t.set_titles(Row::from(vec!["service", "errors"]));
+            t
+        });
+
+        table.sort_by_key(|r| r[0].get_content());
+        table.collapse_groups(0, sum).unwrap();
+        assert_eq!(table.len(), 2);
+        assert_eq!(table[1], Row::from(vec!["web", "6"]));
+        assert_eq!(table.collapse_groups(2, sum), Err(TableError::ColumnOutOfBounds(2)));
+
+        let mut table = Table::new();
+        assert_eq!(table.collapse_groups(0, sum), Err(TableError::ColumnOutOfBounds(0)));
+    }
+
+    #[test]
+    fn dedup() {
+        let mut table = table!(["a", "1"], ["a", "1"], ["b", "2"], ["a", "1"]);
+        table.dedup();
+        assert_eq!(table.len(), 3);
+        let mut table = table!(["a", "1"], ["a", "2"], ["b", "3"], ["b", "4"], ["a", "5"]);
+        table.dedup_by(|r, prev| r[0].get_content() == prev[0].get_content());
+        assert_eq!(table.len(), 3);
+        assert_eq!(table[0][1].get_content(), "1");
+        assert_eq!(table[1][1].get_content(), "3");
+        assert_eq!(table[2][1].get_content(), "5");
+    }
+
+    #[test]
+    fn insert_column() {
+        let mut table = table!(["a", "bc", "def"], ["def"]);
+        table.set_titles(Row::from(vec!["t1", "t2", "t3"]));
+        table.set_column_alignment(2, Alignment::RIGHT);
+        assert_eq!(table.insert_column(4, "x", "y"), Err(TableError::ColumnOutOfBounds(4)));
+        assert!(table.insert_column(1, "new", "-").is_ok());
+        table.add_column("last", "z");
+        assert_eq!(table.get_column_alignment(2), Some(Alignment::LEFT));
+        assert_eq!(table.get_column_alignment(3), Some(Alignment::RIGHT));
+        let out = "\
++-----+-----+----+-----+------+
+| t1  | new | t2 |  t3 | last |
++=====+=====+====+=====+======+
+| a   | -   | bc | def | z    |
++-----+-----+----+-----+------+
+| def | -   |    |     | z    |
++-----+-----+----+-----+------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn pad_last_column() {
+        let mut table = table!(["a", "bc"], ["def", "g"], [H2->"span"]);
+        table.set_format(*FORMAT_CLEAN);
+        assert!(table.get_format().get_pad_last_column());
+        assert_eq!(table.to_string().replace("\r\n", "\n"), " a    bc \n def  g \n span    \n");
+        table.set_pad_last_column(false);
+        assert_eq!(table.to_string().replace("\r\n", "\n"), " a    bc\n def  g\n span\n");
+        // Borders are kept aligned
+        table.set_format(*FORMAT_DEFAULT);
+        table.set_pad_last_column(false);
+        assert!(table.to_string().contains("| def | g  |"));
+    }
+
+    #[test]
+    fn header_separator() {
+        let mut table = table!(["a", "bc"]);
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        table.set_header_separator(format::HeaderSeparator::Blank);
+        assert_eq!(table.get_format().get_header_separator(), format::HeaderSeparator::Blank);
+        let out = "\
++----+----+
+| t1 | t2 |
+|    |    |
+| a  | bc |
++----+----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(table.print(&mut StringWriter::new()).unwrap(), 5);
+        table.set_format(*FORMAT_CLEAN);
+        table.set_header_separator(format::HeaderSeparator::Blank);
+        assert_eq!(table.to_string().replace("\r\n", "\n"), " t1  t2 \n        \n a   bc \n");
+        table.set_header_separator(format::HeaderSeparator::None);
+        assert_eq!(table.to_string().replace("\r\n", "\n"), " t1  t2 \n a   bc \n");
+    }
+
+    #[test]
+    fn column_group_size() {
+        let mut table = table!(["1", "2", "3", "4", "5"], ["6", "7", "8", "9", "10"]);
+        table.set_column_group_size(2);
+        assert_eq!(table.get_format().get_column_group_size(), 2);
+        let out = "\
++---+---+---+---+----+
+| 1 | 2 ‖ 3 | 4 ‖ 5  |
++---+---+---+---+----+
+| 6 | 7 ‖ 8 | 9 ‖ 10 |
++---+---+---+---+----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.set_format(format::FormatBuilder::new()
+                             .column_separator('│')
+                             .column_group_separator('┃')
+                             .borders('│')
+                             .separator(format::LinePosition::Top,
+                                        format::LineSeparator::new('─', '┬', '┌', '┐').group_junction('┰'))
+                             .separator(format::LinePosition::Intern,
+                                        format::LineSeparator::new('─', '┼', '├', '┤').group_junction('╂'))
+                             .separator(format::LinePosition::Bottom,
+                                        format::LineSeparator::new('─', '┴', '└', '┘').group_junction('┸'))
+                             .padding(1, 1)
+                             .column_group_size(2)
+                             .build());
+        let out = "\
+┌───┬───┰───┬───┰────┐
+│ 1 │ 2 ┃ 3 │ 4 ┃ 5  │
+├───┼───╂───┼───╂────┤
+│ 6 │ 7 ┃ 8 │ 9 ┃ 10 │
+└───┴───┸───┴───┸────┘
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.set_column_group_size(0);
+        assert!(!table.to_string().contains('┃'));
+    }
+
+    #[test]
+    fn widest_cells() {
+        let mut table = table!(["a", "bcd", "e"], ["fghij", "k"], ["lm", "nopq"]);
+        assert_eq!(table.widest_cells(), vec![Some((1, 5)), Some((2, 4)), Some((0, 1))]);
+        table.set_titles(Row::from(vec!["title", "t", "long title"]));
+        assert_eq!(table.widest_cells(), vec![Some((crate::TITLE_ROW, 5)), Some((2, 4)), Some((crate::TITLE_ROW, 10))]);
+        assert!(Table::new().widest_cells().is_empty());
+
+        // Cells are measured as displayed
+        let mut table = table!(["a", "1234.5"], ["bc"]);
+        table.set_column_empty_default(2, "unknown".to_string());
+        table.set_column_number_format(1, format::NumberFormat::new().precision(2));
+        assert_eq!(table.widest_cells(), vec![Some((1, 2)), Some((0, 7)), Some((0, 7))]);
+    }
+
+    #[test]
+    fn get_column_mut() {
+        let mut table = table!([" a ", "b"], ["c"], [" d", "e "]);
+        for cell in table.get_column_mut(1).unwrap() {
+            *cell = Cell::new(cell.get_content().trim());
+        }
+        assert_eq!(table[0][1].get_content(), "b");
+        assert_eq!(table[2][1].get_content(), "e");
+        assert_eq!(table[2][0].get_content(), " d");
+        assert_eq!(table.get_column_mut(0).unwrap().count(), 3);
+        assert!(table.get_column_mut(2).is_none());
+    }
+
+    #[test]
+    fn add_computed_column() {
+        let mut table = table!(["a", "6", "3"], ["b", "5"]);
+        table.set_titles(Row::from(vec!["name", "x", "y"]));
+        table.add_computed_column("x/y".to_string(), |r| {
+            let v = |i| r.get_cell(i).and_then(|c| c.get_content().parse::<f64>().ok());
+            match (v(1), v(2)) {
+                (Some(x), Some(y)) => (x / y).to_string(),
+                _ => "n/a".to_string(),
+            }
+        });
+        assert_eq!(table.get_column_num(), 4);
+        assert_eq!(table[0][3].get_content(), "2");
+        assert_eq!(table[1][3].get_content(), "n/a");
+        assert_eq!(table[1][2].get_content(), "");
+
+        let mut table = Table::new();
+        table.set_titles(Row::from(vec!["t"]));
+        table.add_computed_column("c".to_string(), |_| unreachable!());
+        assert!(table.is_empty());
+        assert_eq!(table.get_column_num(), 2);
+    }
+
+    #[test]
+    fn drop_empty_columns() {
+        let mut table = table!(["a", "", "b", " ", ""], ["c", "  ", "d"]);
+        table.set_titles(Row::from(vec!["t1", "t2", "t3", "t4", "t5", "t6"]));
+        table.set_column_alignment(2, Alignment::RIGHT);
+        assert_eq!(table.empty_columns(), vec![1, 3, 4, 5]);
+        table.drop_empty_columns();
+        assert!(table.empty_columns().is_empty());
+        assert_eq!(table, {
+            let mut t = table!(["a", "b"], ["c", "d"]);
+            t.set_titles(Row::from(vec!["t1", "t3"]));
+            t.set_column_alignment(1, Alignment::RIGHT);
+            t
+        });
+
+        let mut table = table!(["a", "", "b"]);
+        table.get_mut_row(0).unwrap().get_mut_cell(0).unwrap().set_hspan(2);
+        assert_eq!(table.empty_columns(), vec![2]);
+        assert!(Table::new().empty_columns().is_empty());
+    }
+
+    #[test]
+    fn blank_after() {
+        let mut table = table!(["b", "2"], ["a", "1"], ["c", "3"]);
+        table.set_format(*FORMAT_NO_LINESEP);
+        table.set_blank_after(1, true);
+        table.set_blank_after(3, true);
+        table.set_blank_after(2, true);
+        table.sort_by_key(|r| r[0].get_content());
+        let expected = "\
++---+---+
+| a | 1 |
+|   |   |
+| b | 2 |
+| c | 3 |
++---+---+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), expected);
+        assert_eq!(table.print(&mut StringWriter::new()).unwrap(), 6);
+
+        table.set_format(*FORMAT_DEFAULT);
+        table.set_blank_after(0, false);
+        table.set_blank_after(1, true);
+        let expected = "\
++---+---+
+| a | 1 |
++---+---+
+| b | 2 |
+|   |   |
++---+---+
+| c | 3 |
++---+---+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), expected);
+
+        let mut table = table!(["a", "1234"], ["b", "5"]);
+        table.set_format(*FORMAT_NO_LINESEP);
+        table.set_blank_after(0, true);
+        table.set_column_number_format(1, format::NumberFormat::new().locale(format::NumberLocale::european()));
+        let expected = "\
++---+-------+
+| a | 1.234 |
+|   |       |
+| b | 5     |
++---+-------+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), expected);
+
+        let mut spaced = table!(["a", "1"]);
+        spaced.set_blank_after(0, true);
+        assert_eq!(spaced, table!(["a", "1"]));
+    }
+
+    #[test]
+    fn rectangular() {
+        let mut table = table!(["a", "b", "c"], ["d"], ["e", "f"], ["g"]);
+        table.get_mut_row(2).unwrap().get_mut_cell(0).unwrap().set_hspan(2);
+        assert!(!table.is_rectangular());
+        assert_eq!(table.make_rectangular("-"), 2);
+        assert!(table.is_rectangular());
+        assert_eq!(table[1], Row::from(vec!["d", "-", "-"]));
+        assert_eq!(table[2].len(), 2);
+        assert_eq!(table[3], Row::from(vec!["g", "-", "-"]));
+        assert_eq!(table.make_rectangular("-"), 0);
+
+        table.set_titles(Row::from(vec!["t1", "t2", "t3", "t4"]));
+        assert!(!table.is_rectangular());
+        assert_eq!(table.make_rectangular(""), 4);
+        assert!(table.is_rectangular());
+        assert!(Table::new().is_rectangular());
+    }
+
+    #[test]
+    fn retain_columns() {
+        let mut table = table!(["a", "1", "x", "2"], ["b", "3", "y"]);
+        table.set_titles(Row::from(vec!["name", "_id", "val", "_tmp"]));
+        table.set_column_alignment(2, Alignment::RIGHT);
+        table.set_column_fixed(3, 4);
+        let mut seen = Vec::new();
+        table.retain_columns(|i, title| {
+            seen.push(i);
+            !title.starts_with('_')
+        });
+        assert_eq!(seen, vec![0, 1, 2, 3]);
+        assert_eq!(table.get_column_alignment(1), Some(Alignment::RIGHT));
+        assert_eq!(table.to_string(), {
+            let mut t = table!(["a", "x"], ["b", "y"]);
+            t.set_titles(Row::from(vec!["name", "val"]));
+            t.set_column_alignment(1, Alignment::RIGHT);
+            t
+        }.to_string());
+
+        let mut table = table!(["a", "b", "c"]);
+        table.retain_columns(|i, title| title.is_empty() && i != 1);
+        assert_eq!(table, table!(["a", "c"]));
+    }
+
+    #[test]
+    fn column_as_bar() {
+        let mut table = table!(["cpu", "50"], ["mem", "100"], ["disk", "n/a"], ["swap", "1.25"]);
+        table.set_column_fixed(1, 15);
+        table.set_column_as_bar(1, 100.0);
+        assert_eq!(table.get_column_bar_max(1), Some(100.0));
+        assert_eq!(table.get_column_bar_max(0), None);
+        assert_eq!(table[0][1].get_content(), "50");
+        let out = "\
++------+-----------------+
+| cpu  | █████      50   |
++------+-----------------+
+| mem  | ██████████ 100  |
++------+-----------------+
+| disk | n/a             |
++------+-----------------+
+| swap | ▏          1.25 |
++------+-----------------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+
+        let mut table = table!(["a", "5"], ["b", "10"]);
+        table.set_titles(Row::from(vec!["name", "quantity"]));
+        table.set_column_as_bar(1, 10.0);
+        let out = "\
++------+----------+
+| name | quantity |
++======+==========+
+| a    | ██▌   5  |
++------+----------+
+| b    | █████ 10 |
++------+----------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.unset_column_as_bar(1);
+        assert_eq!(table.get_column_bar_max(1), None);
+        table.unset_column_as_bar(5);
+    }
+
+    #[test]
+    fn total_footer() {
+        let mut table = table!(["apples", "3", "0.5", "x"], ["pears", "n/a", "1.25", "y"], ["plums", "4", "2", "z"]);
+        assert!(table.add_total_footer(0, &[1, 2]).is_ok());
+        assert_eq!(table.len(), 4);
+        assert_eq!(table[3][0].get_content(), "Total");
+        assert_eq!(table[3][1].get_content(), "7");
+        assert_eq!(table[3][2].get_content(), "3.75");
+        assert_eq!(table[3][3].get_content(), "");
+        assert_eq!(table.add_total_footer(0, &[4]), Err(TableError::ColumnOutOfBounds(4)));
+        assert_eq!(table.add_total_footer(5, &[1]), Err(TableError::ColumnOutOfBounds(5)));
+        assert_eq!(table.len(), 4);
+
+        let mut table = table!(["1.5e3"], ["NaN"], ["inf"], ["2.50"], ["12.5E-1"]);
+        table.add_total_footer(0, &[0]).unwrap();
+        assert_eq!(table[5][0].get_content(), "1503.75");
+
+        let mut table = table!([H2->"ab", "3"], ["x", "y", "4"]);
+        table.add_total_footer(0, &[1, 2]).unwrap();
+        assert_eq!(table[2][1].get_content(), "0");
+        assert_eq!(table[2][2].get_content(), "7");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn set_element() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        assert!(table.set_element("foo", 12, 12).is_err());
+        assert!(table.set_element("foo", 1, 1).is_ok());
+        assert_eq!(table[1][1].get_content(), "foo");
+    }
+
+    #[test]
+    fn set_cell() {
+        let mut table = table!(["a", "bc", "def"], ["def", "bc", "a"]);
+        assert_eq!(table.set_cell(2, 0, "foo"), Err(TableError::RowOutOfBounds(2)));
+        assert_eq!(table.set_cell(1, 3, "foo"), Err(TableError::ColumnOutOfBounds(3)));
+        assert!(table.set_cell(1, 2, 42).is_ok());
+        assert_eq!(table[1][2].get_content(), "42");
+        assert_eq!(table[0][2].get_content(), "def");
+    }
+
+    #[test]
+    fn cell_span() {
+        let mut table = table!(["a", "b", "c"], ["too long to fit", "d", "e"], ["f", "g", "h"]);
+        assert!(table.set_cell_span(1, 0, 3).is_ok());
+        assert_eq!(table[1].len(), 1);
+        assert_eq!(table[1][0].get_hspan(), 3);
+        let out = "\
++------+------+------+
+| a    | b    | c    |
++------+------+------+
+| too long to fit    |
++--------------------+
+| f    | g    | h    |
++------+------+------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert!(table.set_cell_span(2, 1, 2).is_ok());
+        assert_eq!(table[2].len(), 2);
+        assert_eq!(table[2][1].get_content(), "g");
+        assert_eq!(table.set_cell_span(0, 1, 3), Err(TableError::ColumnOutOfBounds(3)));
+        assert_eq!(table.set_cell_span(5, 0, 1), Err(TableError::RowOutOfBounds(5)));
+
+        let mut table = table!(["a", "b", "c"], ["d", "e", "f"]);
+        assert!(table.set_cell_span(0, 0, 3).is_ok());
+        assert!(table.set_cell_span(0, 0, 2).is_ok());
+        assert_eq!(table[0].len(), 2);
+        assert_eq!(table[0][0].get_hspan(), 2);
+        assert_eq!(table[0][1].get_content(), "");
+        let out = "\
++---+---+---+
+| a     |   |
++-------+---+
+| d | e | f |
++---+---+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert!(table.set_cell_span(0, 0, 3).is_ok());
+        assert_eq!(table[0].len(), 1);
+        assert_eq!(table[0][0].get_content(), "a");
+        assert!(table.set_cell_span(0, 1, 2).is_ok());
+        assert_eq!(table[0].len(), 2);
+        assert_eq!(table[0][0].get_hspan(), 1);
+        assert_eq!(table[0][1].get_hspan(), 2);
+        assert_eq!(table[0][1].get_content(), "");
+        assert!(table.set_cell_span(1, 0, 1).is_ok());
+        assert_eq!(table[1].len(), 3);
+    }
+
+    #[test]
+    fn no_linesep() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_NO_LINESEP);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
++-----+--------+-----+
+| t1  | t2     | t3  |
+| a   | bc     | def |
+| def | newval | a   |
++-----+--------+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(5, table.print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn no_colsep() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_NO_COLSEP);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
+------------------
+ t1   t2      t3 \n\
+==================
+ a    bc      def \n\
+------------------
+ def  newval  a \n\
+------------------
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n", "\n"));
         assert_eq!(table.to_string().replace("\r\n", "\n"), out);
         assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
-        table.unset_titles();
+    }
+
+    #[test]
+    fn clean() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_CLEAN);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
+\u{0020}t1   t2      t3 \n\
+\u{0020}a    bc      def \n\
+\u{0020}def  newval  a \n\
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(3, table.print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn padding() {
+        let mut table = Table::new();
+        let mut format = *FORMAT_DEFAULT;
+        format.padding(2, 2);
+        table.set_format(format);
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        assert_eq!(table[1][1].get_content(), "bc");
+
+        table[1][1] = Cell::new("newval");
+        assert_eq!(table[1][1].get_content(), "newval");
+
+        let out = "\
++-------+----------+-------+
+|  t1   |  t2      |  t3   |
++=======+==========+=======+
+|  a    |  bc      |  def  |
++-------+----------+-------+
+|  def  |  newval  |  a    |
++-------+----------+-------+
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn indent() {
+        let mut table = Table::new();
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
+        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        table.get_format().indent(8);
+        let out = "        +-----+----+-----+
+        | t1  | t2 | t3  |
+        +=====+====+=====+
+        | a   | bc | def |
+        +-----+----+-----+
+        | def | bc | a   |
+        +-----+----+-----+
+";
+        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn as_slice_follows_table() {
+        let mut table = table!([1, 2], [3, 4]);
+        assert_eq!(2, table.as_slice().len());
+        table.add_row(Row::from(vec![5, 6]));
+        table.set_titles(Row::from(vec!["a", "b"]));
+        let slice = table.as_slice();
+        assert_eq!(3, slice.len());
+        assert_eq!(table.to_string(), slice.to_string());
+        assert_eq!(2, AsRef::<TableSlice>::as_ref(&slice).slice(1..).len());
+        assert_eq!(2, table.slice(1..).len());
+    }
+
+    #[test]
+    fn slices() {
+        let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        table.add_row(Row::new(vec![Cell::new("0"), Cell::new("0"), Cell::new("0")]));
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
+        table.add_row(Row::new(vec![Cell::new("3"), Cell::new("3"), Cell::new("3")]));
+        table.add_row(Row::new(vec![Cell::new("4"), Cell::new("4"), Cell::new("4")]));
+        table.add_row(Row::new(vec![Cell::new("5"), Cell::new("5"), Cell::new("5")]));
+        let out = "\
++----+----+----+
+| t1 | t2 | t3 |
++====+====+====+
+| 1  | 1  | 1  |
++----+----+----+
+| 2  | 2  | 2  |
++----+----+----+
+| 3  | 3  | 3  |
++----+----+----+
+";
+        let slice = table.slice(..);
+        let slice = slice.slice(1..);
+        let slice = slice.slice(..3);
+        assert_eq!(out, slice.to_string().replace("\r\n", "\n"));
+        assert_eq!(9, slice.print(&mut StringWriter::new()).unwrap());
+        assert_eq!(out, table.slice(1..4).to_string().replace("\r\n", "\n"));
+        assert_eq!(9, table.slice(1..4).print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn test_unicode_separators() {
+        let mut table = Table::new();
+        table.set_format(*FORMAT_BOX_CHARS);
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        let out = "\
+┌────┬────┬────┐
+│ t1 │ t2 │ t3 │
+├────┼────┼────┤
+│ 1  │ 1  │ 1  │
+├────┼────┼────┤
+│ 2  │ 2  │ 2  │
+└────┴────┴────┘
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n", "\n"));
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+    }
+
+    #[test]
+    fn test_readme_format() {
+
+        // The below is lifted from the README
+
+        let mut table = Table::new();
+        let format = format::FormatBuilder::new()
+            .column_separator('|')
+            .borders('|')
+            .separators(&[format::LinePosition::Top,
+                        format::LinePosition::Bottom],
+                        format::LineSeparator::new('-', '+', '+', '+'))
+            .padding(1, 1)
+            .build();
+        table.set_format(format);
+
+        table.set_titles(Row::new(vec![Cell::new("Title 1"), Cell::new("Title 2")]));
+        table.add_row(Row::new(vec![Cell::new("Value 1"), Cell::new("Value 2")]));
+        table.add_row(Row::new(vec![Cell::new("Value three"), Cell::new("Value four")]));
+
         let out = "\
-+-----+----+-----+
-| a   | bc | def |
-+-----+----+-----+
-| def | bc | a   |
-+-----+----+-----+
++-------------+------------+
+| Title 1     | Title 2    |
+| Value 1     | Value 2    |
+| Value three | Value four |
++-------------+------------+
 ";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
+
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n","\n"));
+        assert_eq!(out, table.to_string().replace("\r\n","\n"));
         assert_eq!(5, table.print(&mut StringWriter::new()).unwrap());
     }
 
     #[test]
-    fn index() {
+    fn test_readme_format_with_title() {
         let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
+        table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
 
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
+        table.set_titles(Row::new(vec![Cell::new("Title 1"), Cell::new("Title 2")]));
+        table.add_row(Row::new(vec![Cell::new("Value 1"), Cell::new("Value 2")]));
+        table.add_row(Row::new(vec![Cell::new("Value three"), Cell::new("Value four")]));
 
         let out = "\
-+-----+--------+-----+
-| t1  | t2     | t3  |
-+=====+========+=====+
-| a   | bc     | def |
-+-----+--------+-----+
-| def | newval | a   |
-+-----+--------+-----+
++-------------+------------+
+| Title 1     | Title 2    |
++-------------+------------+
+| Value 1     | Value 2    |
+| Value three | Value four |
++-------------+------------+
 ";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
-        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n","\n"));
+        assert_eq!(out, table.to_string().replace("\r\n","\n"));
+        assert_eq!(6, table.print(&mut StringWriter::new()).unwrap());
     }
 
     #[test]
-    fn table_size() {
+    fn border_style() {
         let mut table = Table::new();
-        assert!(table.is_empty());
-        assert!(table.as_ref().is_empty());
-        assert_eq!(table.len(), 0);
-        assert_eq!(table.as_ref().len(), 0);
-        assert_eq!(table.get_column_num(), 0);
-        assert_eq!(table.as_ref().get_column_num(), 0);
-        table.add_empty_row();
-        assert!(!table.is_empty());
-        assert!(!table.as_ref().is_empty());
-        assert_eq!(table.len(), 1);
-        assert_eq!(table.as_ref().len(), 1);
-        assert_eq!(table.get_column_num(), 0);
-        assert_eq!(table.as_ref().get_column_num(), 0);
-        table[0].add_cell(Cell::default());
-        assert_eq!(table.get_column_num(), 1);
-        assert_eq!(table.as_ref().get_column_num(), 1);
+        table.set_format(format::BorderStyle::rounded().into());
+        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
+        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        let out = "\
+╭────┬────┬────╮
+│ t1 │ t2 │ t3 │
+├────┼────┼────┤
+│ 1  │ 1  │ 1  │
+├────┼────┼────┤
+│ 2  │ 2  │ 2  │
+╰────┴────┴────╯
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        let unicode: format::TableFormat = format::BorderStyle::unicode().into();
+        assert_eq!(unicode, *FORMAT_BOX_CHARS);
     }
 
     #[test]
-    fn get_row() {
-        let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        assert!(table.get_row(12).is_none());
-        assert!(table.get_row(1).is_some());
-        assert_eq!(table.get_row(1).unwrap()[0].get_content(), "def");
-        assert!(table.get_mut_row(12).is_none());
-        assert!(table.get_mut_row(1).is_some());
-        table.get_mut_row(1).unwrap().add_cell(Cell::new("z"));
-        assert_eq!(table.get_row(1).unwrap()[3].get_content(), "z");
+    fn border_style_builder() {
+        let style = format::BorderStyleBuilder::new()
+            .horizontal('═')
+            .cross('╬')
+            .build()
+            .unwrap();
+        assert_eq!(style.builder().build(), Ok(style));
+        assert!(format::BorderStyleBuilder::new().vertical('\u{301}').build().is_err());
+        assert!(format::BorderStyleBuilder::new().top_left('\t').build().is_err());
+        assert_eq!(format::BorderStyle::ascii().builder().cross('十').build(),
+                   Err(TableError::InvalidGlyph("Cross", '十')));
     }
 
     #[test]
-    fn add_empty_row() {
+    fn span_junctions() {
         let mut table = Table::new();
-        assert_eq!(table.len(), 0);
-        table.add_empty_row();
-        assert_eq!(table.len(), 1);
-        assert_eq!(table[0].len(), 0);
+        table.set_format(*FORMAT_BOX_CHARS);
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+        table.add_row(Row::new(vec![Cell::new("span 2").with_hspan(2), Cell::new("a")]));
+        table.add_row(Row::new(vec![Cell::new("b"), Cell::new("c"), Cell::new("d")]));
+        table.add_row(Row::new(vec![Cell::new("e"), Cell::new("span 2").with_hspan(2)]));
+        let out = "\
+┌────┬────┬────┐
+│ t1 │ t2 │ t3 │
+├────┼────┼────┤
+│ span 2  │ a  │
+├─────────┼────┤
+│ b  │ c  │ d  │
+├────┼────┼────┤
+│ e  │ span 2  │
+└────┴─────────┘
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        let mut out = StringWriter::new();
+        table.render_rows(&mut out, table.row_iter().skip(2), &[2, 2, 2], false).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "┌────┬────┬────┐\n│ e  │ span 2  │\n└────┴─────────┘\n");
     }
 
     #[test]
-    fn remove_row() {
+    fn test_horizontal_span() {
         let mut table = Table::new();
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2").with_hspan(2)]));
         table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.remove_row(12);
-        assert_eq!(table.len(), 2);
-        table.remove_row(0);
-        assert_eq!(table.len(), 1);
-        assert_eq!(table[0][0].get_content(), "def");
+        table.add_row(Row::new(vec![Cell::new("def").style_spec("H02c"), Cell::new("a")]));
+        let out = "\
++----+----+-----+
+| t1 | t2       |
++====+==========+
+| a  | bc | def |
++----+----+-----+
+|   def   | a   |
++---------+-----+
+";
+        println!("{}", out);
+        println!("____");
+        println!("{}", table.to_string().replace("\r\n","\n"));
+        assert_eq!(out, table.to_string().replace("\r\n","\n"));
+        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
     }
 
     #[test]
-    fn insert_row() {
-        let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.insert_row(12,
-                         Row::new(vec![Cell::new("1"), Cell::new("2"), Cell::new("3")]));
-        assert_eq!(table.len(), 3);
-        assert_eq!(table[2][1].get_content(), "2");
-        table.insert_row(1,
-                         Row::new(vec![Cell::new("3"), Cell::new("4"), Cell::new("5")]));
-        assert_eq!(table.len(), 4);
-        assert_eq!(table[1][1].get_content(), "4");
-        assert_eq!(table[2][1].get_content(), "bc");
+    fn span_past_last_column() {
+        let mut table = table!(["a", "b"]);
+        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2").with_hspan(3)]));
+        let out = "\
++----+---+
+| t1 | t |
++====+===+
+| a  | b |
++----+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
     }
 
     #[test]
-    fn set_element() {
+    fn debug() {
         let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        assert!(table.set_element("foo", 12, 12).is_err());
-        assert!(table.set_element("foo", 1, 1).is_ok());
-        assert_eq!(table[1][1].get_content(), "foo");
+        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc")]));
+        let out = "\
+Table
++---+----+
+| a | bc |
++---+----+
+";
+        assert_eq!(format!("{:?}", table).replace("\r\n", "\n"), out);
+        assert_eq!(format!("{:?}", table.slice(..)).replace("\r\n", "\n"),
+                   out.replace("Table", "TableSlice"));
+        let alternate = format!("{:#?}", table).replace("\r\n", "\n");
+        assert!(alternate.starts_with(out));
+        assert!(alternate.contains("rows: ["));
     }
 
     #[test]
-    fn no_linesep() {
+    fn horizontal_overflow() {
+        let mut table = table!(["a", "bc", "def", "ghij"], ["k", "lm", "nop", "qrst"]);
+        table.set_titles(Row::from(vec!["t1", "t2", "t3", "t4"]));
+        table.get_format().max_width(Some(20));
+        let out = "\
++----+----+-----+------+
+| t1 | t2 | t3  | t4   |
++====+====+=====+======+
+| a  | bc | def | ghij |
++----+----+-----+------+
+| k  | lm | nop | qrst |
++----+----+-----+------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+
+        table.get_format().horizontal_overflow(format::HOverflow::Indicator);
+        let out = "\
++----+----+---+
+| t1 | t2 | → |
++====+====+===+
+| a  | bc | → |
++----+----+---+
+| k  | lm | → |
++----+----+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+
+        table.get_format().horizontal_overflow(format::HOverflow::WrapColumns);
+        let out = "\
++----+----+-----+
+| t1 | t2 | t3  |
++====+====+=====+
+| a  | bc | def |
++----+----+-----+
+| k  | lm | nop |
++----+----+-----+
++------+
+| t4   |
++======+
+| ghij |
++------+
+| qrst |
++------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(14, table.print(&mut StringWriter::new()).unwrap());
+
+        let mut table = table!([H3->"spanning", "x"], ["y"]);
+        table.get_format().max_width(Some(12));
+        table.get_format().horizontal_overflow(format::HOverflow::Indicator);
+        let out = "\
++------+---+
+| span | → |
++------+---+
+| y    | → |
++------+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+
+        let mut table = table!(["a long single column"], ["short"]);
+        table.get_format().max_width(Some(10));
+        table.set_horizontal_overflow(format::HOverflow::Indicator);
+        let out = "\
++----+---+
+| a  | → |
++----+---+
+| sh | → |
++----+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.get_format().max_width(Some(14));
+        let out = "\
++--------+---+
+| a long | → |
++--------+---+
+| short  | → |
++--------+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn column_alignment() {
+        let mut table = table!(["a", "bc", "def"], ["def", "bc", "a"]);
+        table[1][2].align(Alignment::LEFT);
+        assert_eq!(table.get_column_alignment(0), Some(Alignment::LEFT));
+        assert_eq!(table.get_column_alignment(3), None);
+        table.get_format().alignment(Alignment::CENTER);
+        table.set_column_alignment(2, Alignment::RIGHT);
+        assert_eq!(table.get_column_alignment(0), Some(Alignment::CENTER));
+        assert_eq!(table.get_column_alignment(2), Some(Alignment::RIGHT));
+        assert_eq!(table.slice(1..).get_column_alignment(2), Some(Alignment::RIGHT));
+        let out = "\
++-----+----+-----+
+|  a  | bc | def |
++-----+----+-----+
+| def | bc | a   |
++-----+----+-----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.unset_column_alignment(2);
+        assert_eq!(table.get_column_alignment(2), Some(Alignment::CENTER));
+        let mut writer = StringWriter::new();
+        table.print_html(&mut writer).unwrap();
+        assert!(writer.as_string().starts_with("<table><tr><td style=\"text-align: center;\">a</td>"));
+    }
+
+    /// Terminal writing `<b>`, `<d>` and `<r>` for bold, dim and reset
+    fn test_terminal() -> TerminfoTerminal<Vec<u8>> {
+        let mut strings = HashMap::new();
+        strings.insert("bold", b"<b>".to_vec());
+        strings.insert("dim", b"<d>".to_vec());
+        strings.insert("sgr0", b"<r>".to_vec());
+        strings.insert("setaf", b"<f%p1%d>".to_vec());
+        strings.insert("setab", b"<g%p1%d>".to_vec());
+        let mut numbers = HashMap::new();
+        numbers.insert("colors", 16);
+        let info = TermInfo {
+            names: vec!["test".to_string()],
+            bools: HashMap::new(),
+            numbers,
+            strings,
+        };
+        TerminfoTerminal::new_with_terminfo(Vec::new(), info)
+    }
+
+    #[test]
+    fn adaptive_colors() {
+        let mut table = table!([Fw->"white", Fb->"blue", FwBd->"boxed"]);
+        let mut term = test_terminal();
+        table.print_term(&mut term).unwrap();
+        let plain = String::from_utf8(term.into_inner()).unwrap();
+        assert!(plain.contains("<f7>white") && plain.contains("<f4>blue"));
+
+        let mut term = test_terminal();
+        table.as_slice().print_term_on(&mut term, Some(Background::Light)).unwrap();
+        assert_eq!(String::from_utf8(term.into_inner()).unwrap(), plain);
+
+        table.set_adaptive_colors(true);
+        assert!(table.get_adaptive_colors());
+        let mut term = test_terminal();
+        table.as_slice().print_term_on(&mut term, Some(Background::Light)).unwrap();
+        let light = String::from_utf8(term.into_inner()).unwrap();
+        let mut term = test_terminal();
+        table.as_slice().print_term_on(&mut term, Some(Background::Dark)).unwrap();
+        let dark = String::from_utf8(term.into_inner()).unwrap();
+        let mut term = test_terminal();
+        table.as_slice().print_term_on(&mut term, None).unwrap();
+        let unknown = String::from_utf8(term.into_inner()).unwrap();
+
+        assert!(light.contains("<f0>white") && light.contains("<f4>blue") && light.contains("<f7><g0>boxed"));
+        assert!(dark.contains("<f7>white") && dark.contains("<f12>blue") && dark.contains("<f7><g0>boxed"));
+        assert_eq!(unknown, plain);
+    }
+
+    #[test]
+    fn on_error() {
+        use std::io::{self, Write};
+        /// Writer accepting `capacity` bytes, then failing, and counting flushes
+        struct Failing {
+            capacity: usize,
+            flushes: usize,
+        }
+        impl Write for Failing {
+            fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+                if data.len() > self.capacity {
+                    return Err(io::Error::new(io::ErrorKind::BrokenPipe, "closed"));
+                }
+                self.capacity -= data.len();
+                Ok(data.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                self.flushes += 1;
+                Ok(())
+            }
+        }
+
+        let mut table = table!(["a", "b"], ["c", "d"]);
+        assert_eq!(table.get_on_error(), OnError::Abort);
+        let mut out = Failing { capacity: 20, flushes: 0 };
+        assert_eq!(table.print(&mut out).unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(out.flushes, 0);
+
+        table.set_on_error(OnError::FlushAndAbort);
+        let mut out = Failing { capacity: 20, flushes: 0 };
+        assert_eq!(table.print(&mut out).unwrap_err().kind(), io::ErrorKind::BrokenPipe);
+        assert_eq!(out.flushes, 1);
+        let mut out = Failing { capacity: 1000, flushes: 0 };
+        assert_eq!(table.print(&mut out).unwrap(), 5);
+        assert_eq!(out.flushes, 1);
+    }
+
+    #[test]
+    fn emphasis_stride() {
+        let mut table = table!(["r0"], ["r1"], ["r2"], ["r3"], [Fr->"r4"]);
+        let mut term = test_terminal();
+        table.print_term(&mut term).unwrap();
+        let plain = String::from_utf8(term.into_inner()).unwrap();
+        let text = table.to_string();
+
+        table.set_emphasis_stride(2);
+        assert_eq!(table.get_emphasis_stride(), 2);
+        let mut term = test_terminal();
+        table.print_term(&mut term).unwrap();
+        let emphasized = String::from_utf8(term.into_inner()).unwrap();
+        assert_eq!(table.to_string(), text);
+        assert!(!plain.contains("<b>"));
+        assert_eq!(emphasized.matches("<b>").count(), 2);
+        assert!(emphasized.contains("<b>r1") && emphasized.contains("<b>r3"));
+        assert!(emphasized.contains("<f1>r4"));
+    }
+
+    #[test]
+    fn continuation_style() {
+        let mut table = table!([b->"a\nb\nc", "d"]);
+        table.get_format().continuation_style(Attr::Dim);
+        let plain = table.to_string();
+        let mut term = test_terminal();
+        assert_eq!(5, table.print_term(&mut term).unwrap());
+        let out = "\
++---+---+
+| <b>a<r> | d<r> |
+| <b><d>b<r> |  <r> |
+| <b><d>c<r> |  <r> |
++---+---+
+";
+        assert_eq!(out, String::from_utf8(term.into_inner()).unwrap().replace("\r\n", "\n"));
+        // Layout and plain output are unchanged
+        table.get_format().continuation_style(Attr::Bold);
+        assert_eq!(plain, table.to_string());
+    }
+
+    #[test]
+    fn titles_visible() {
+        let mut table = table!(["a", "b"]);
+        table.set_titles(Row::from(vec!["long title", "t"]));
+        table.set_titles_visible(false);
+        assert!(!table.get_titles_visible());
+        let out = "\
++---+---+
+|   |   |
++===+===+
+| a | b |
++---+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        let mut many = StringWriter::new();
+        Table::print_many(&[&table], &mut many).unwrap();
+        assert_eq!(out, many.as_string().replace("\r\n", "\n"));
+        table.set_titles_visible(true);
+        assert!(table.to_string().contains("long title"));
+    }
+
+    #[test]
+    fn flush_per_row() {
+        use std::io::{self, Write};
+        struct FlushCounter(Vec<usize>, usize);
+        impl Write for FlushCounter {
+            fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+                self.1 += buf.len();
+                Ok(buf.len())
+            }
+            fn flush(&mut self) -> io::Result<()> {
+                self.0.push(self.1);
+                Ok(())
+            }
+        }
+        let mut table = table!(["a"], ["b"], ["c"]);
+        let mut out = FlushCounter(Vec::new(), 0);
+        table.print(&mut out).unwrap();
+        assert_eq!(out.0.len(), 1);
+        table.set_flush_per_row(true);
+        assert!(table.get_flush_per_row());
+        let mut out = FlushCounter(Vec::new(), 0);
+        table.print(&mut out).unwrap();
+        // One flush per row, and the final one
+        assert_eq!(out.0, vec![12, 24, 36, 42]);
+    }
+
+    #[test]
+    fn render_rows() {
         let mut table = Table::new();
+        table.set_titles(Row::from(vec!["name", "n"]));
+        table.set_column_alignment(1, Alignment::RIGHT);
+        let master = [Row::from(vec!["alice", "3", "extra"]), Row::from(vec!["bob", "12"]), Row::from(vec!["carol", "7"])];
+        let mut out = StringWriter::new();
+        table.render_rows(&mut out, master.iter().filter(|r| r[0].get_content() != "bob"), &[5, 2], true).unwrap();
+        let expected = "\
++-------+----+
+| name  |  n |
++=======+====+
+| alice |  3 |
++-------+----+
+| carol |  7 |
++-------+----+
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+        let mut out = StringWriter::new();
+        table.render_rows(&mut out, &master[1..2], &[5, 2], false).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "+-------+----+\n| bob   | 12 |\n+-------+----+\n");
+
+        // Rows are displayed with the column settings of the table
+        table.set_column_merge_vertical(0, true);
+        table.set_column_empty_default(1, "-".to_string());
+        table.set_titles_visible(false);
+        let rows = [Row::from(vec!["alice", "3"]), Row::from(vec!["alice", ""])];
+        let mut out = StringWriter::new();
+        table.render_rows(&mut out, &rows, &[5, 2], true).unwrap();
+        let expected = "\
++-------+----+
+|       |    |
++=======+====+
+| alice |  3 |
++-------+----+
+|       |  - |
++-------+----+
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+    }
+
+    #[test]
+    fn print_header() {
+        let mut table = table!(["a", "bc"], ["d", "e"]);
+        let mut out = StringWriter::new();
+        table.print_header(&mut out, &[3, 3]).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "+-----+-----+\n");
+
+        table.set_titles(Row::from(vec!["title1", "t2", "t3"]));
+        let mut out = StringWriter::new();
+        table.print_header(&mut out, &[3, 3]).unwrap();
+        table.append_rows_to_writer(&mut out, 0, &[3, 3]).unwrap();
+        let expected = "\
++-----+-----+
+| tit | t2  |
++=====+=====+
+| a   | bc  |
++-----+-----+
+| d   | e   |
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+    }
+
+    #[test]
+    fn append_rows_to_writer() {
+        let mut table = table!(["a", "bc"]);
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        let mut out = StringWriter::new();
+        table.append_rows_to_writer(&mut out, 0, &[3, 3]).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "| a   | bc  |\n");
+
+        table.add_row(Row::from(vec!["defgh", "i", "extra"]));
+        let mut out = StringWriter::new();
+        table.append_rows_to_writer(&mut out, 1, &[3, 3]).unwrap();
+        let expected = "\
++-----+-----+
+| def | i   |
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+        let mut out = StringWriter::new();
+        table.append_rows_to_writer(&mut out, 2, &[3, 3]).unwrap();
+        assert_eq!(out.as_string(), "");
+    }
+
+    #[test]
+    fn display_field_alignment() {
+        let table = table!(["a", "bc"]);
+        let lines = ["+---+----+", "| a | bc |", "+---+----+"];
+        let check = |out: String, pad: &dyn Fn(&str) -> String| {
+            let expected: Vec<String> = lines.iter().map(|l| pad(l)).collect();
+            assert_eq!(out.replace("\r\n", "\n").lines().collect::<Vec<_>>(), expected);
+        };
+        check(format!("{:14}", table), &|l| format!("{}    ", l));
+        check(format!("{:>14}", table), &|l| format!("    {}", l));
+        check(format!("{:*^14}", table), &|l| format!("**{}**", l));
+        check(format!("{:^15}", table), &|l| format!("  {}   ", l));
+        check(format!("{:^5}", table), &|l| l.to_string());
+        assert_eq!(format!("{}", table), table.to_string());
+    }
+
+    #[test]
+    fn print_highlighted() {
+        let mut table = table!(["foo bar", "baz"], ["barbar", "qux"]);
+        table.set_titles(Row::from(vec!["bar", "t"]));
+        let mut out = StringWriter::new();
+        table.print_highlighted(&mut out, "bar").unwrap();
+        let expected = "\
++----------------+-----+
+| >>bar<<        | t   |
++================+=====+
+| foo >>bar<<    | baz |
++----------------+-----+
+| >>bar<<>>bar<< | qux |
++----------------+-----+
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+
+        let mut out = test_terminal();
+        table.print_term_highlighted(&mut out, "qux").unwrap();
+        let mut expected = test_terminal();
+        table.set_cell(1, 1, "\x1b[7mqux\x1b[27m").unwrap();
+        table.print_term(&mut expected).unwrap();
+        assert_eq!(out.into_inner(), expected.into_inner());
+        assert!(table.to_string().starts_with("+---------+-----+"));
+    }
+
+    #[test]
+    fn print_highlighted_displays_rows() {
+        let mut table = table!(["a", "1234"], ["b", "5"]);
         table.set_format(*FORMAT_NO_LINESEP);
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
+        table.set_blank_after(0, true);
+        table.set_column_number_format(1, format::NumberFormat::new().locale(format::NumberLocale::european()));
+        let mut out = StringWriter::new();
+        table.print_highlighted(&mut out, "234").unwrap();
+        let expected = "\
++---+-----------+
+| a | 1.>>234<< |
+|   |           |
+| b | 5         |
++---+-----------+
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+    }
 
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
+    #[test]
+    fn print_rotated() {
+        let mut table = table!(["a", "bc", "def"], ["multi\nline", "x"]);
+        table.set_titles(Row::from(vec!["id", "name"]));
+        let out = "\
+id  : a
+name: bc
+2   : def
+-----------
+id  : multi
+      line
+name: x
+2   : \n";
+        let mut writer = StringWriter::new();
+        assert_eq!(8, table.print_rotated(&mut writer).unwrap());
+        assert_eq!(out, writer.as_string().replace("\r\n", "\n"));
+    }
 
+    #[test]
+    fn fit_to_width() {
+        let mut table = table!(["ok", "waiting for the build"],
+                               ["ok", "deploying to staging"],
+                               ["timed out while contacting the remote registry", "retrying"]);
+        table.fit_to_width(44, format::FitStrategy::Proportional);
+        let proportional = table.to_string().replace("\r\n", "\n");
+        table.fit_to_width(44, format::FitStrategy::Balanced);
+        let balanced = table.to_string().replace("\r\n", "\n");
+        assert!(proportional.lines().all(|l| display_width(l) <= 44));
+        assert!(balanced.lines().all(|l| display_width(l) <= 44));
         let out = "\
-+-----+--------+-----+
-| t1  | t2     | t3  |
-| a   | bc     | def |
-| def | newval | a   |
-+-----+--------+-----+
++-----------------------+--------------+
+| ok                    | waiting for  |
+|                       | the build    |
++-----------------------+--------------+
+| ok                    | deploying to |
+|                       | staging      |
++-----------------------+--------------+
+| timed out while       | retrying     |
+| contacting the remote |              |
+| registry              |              |
++-----------------------+--------------+
 ";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
-        assert_eq!(5, table.print(&mut StringWriter::new()).unwrap());
+        assert_eq!(proportional, out);
+        let out = "\
++-----------------+-----------------------+
+| ok              | waiting for the build |
++-----------------+-----------------------+
+| ok              | deploying to staging  |
++-----------------+-----------------------+
+| timed out while | retrying              |
+| contacting the  |                       |
+| remote registry |                       |
++-----------------+-----------------------+
+";
+        assert_eq!(balanced, out);
+
+        // Fitting again with more room unwraps cells
+        table.fit_to_width(200, format::FitStrategy::Balanced);
+        assert_eq!(table.to_string().lines().count(), 7);
     }
 
     #[test]
-    fn no_colsep() {
-        let mut table = Table::new();
-        table.set_format(*FORMAT_NO_COLSEP);
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
-
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
+    fn column_fixed() {
+        let mut table = table!(["foobarbaz", "a", "abcdefg"], ["qux", "bc", "d"]);
+        table.set_titles(Row::from(vec!["Title", "T", "Centered"]));
+        table.set_column_fixed(0, 5);
+        table.set_column_fixed(1, 4);
+        table.set_column_fixed(2, 5);
+        table.set_column_alignment(0, Alignment::RIGHT);
+        table.set_column_alignment(2, Alignment::CENTER);
+        assert_eq!(table.get_column_fixed(1), Some(4));
+        let out = "\
++-------+------+-------+
+| Title | T    | ‹nte› |
++=======+======+=======+
+| ‹rbaz | a    | ‹cde› |
++-------+------+-------+
+|   qux | bc   |   d   |
++-------+------+-------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.unset_column_fixed(0);
+        table.unset_column_fixed(1);
+        table.unset_column_fixed(2);
+        assert_eq!(table.get_column_fixed(1), None);
+        assert_eq!(table.to_string().lines().next(), Some("+-----------+----+----------+"));
+    }
 
+    #[test]
+    fn column_number_format() {
+        let mut table = table!(["1234", "3400000"], ["12", "n/a"]);
+        table.set_titles(crate::row!["requests", "bytes"]);
+        table.set_column_number_format(1, format::NumberFormat::new().compact(2));
+        assert_eq!(table.get_column_number_format(1), Some(format::NumberFormat::new().compact(2)));
+        assert_eq!(table.get_column_number_format(0), None);
         let out = "\
-------------------
- t1   t2      t3 \n\
-==================
- a    bc      def \n\
-------------------
- def  newval  a \n\
-------------------
++----------+-------+
+| requests | bytes |
++==========+=======+
+| 1234     | 3.4M  |
++----------+-------+
+| 12       | n/a   |
++----------+-------+
 ";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n", "\n"));
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
-        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(table[0][1].get_content(), "3400000");
+        table.unset_column_number_format(1);
+        assert!(table.to_string().contains("3400000"));
     }
 
     #[test]
-    fn clean() {
-        let mut table = Table::new();
-        table.set_format(*FORMAT_CLEAN);
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
+    fn column_number_locale() {
+        use format::{NumberFormat, NumberLocale};
+        let mut table = table!(["1234567.5", "1234567.5", "1500", "1e3"]);
+        table.set_column_number_format(0, NumberFormat::new().locale(NumberLocale::european()));
+        table.set_column_number_format(1, NumberFormat::new().locale(NumberLocale::new(None, '·')));
+        table.set_column_number_format(2, NumberFormat::new().compact(2).locale(NumberLocale::european()));
+        table.set_column_number_format(3, NumberFormat::new().locale(NumberLocale::swiss()));
+        let out = "\
++-------------+-----------+------+-----+
+| 1.234.567,5 | 1234567·5 | 1,5k | 1e3 |
++-------------+-----------+------+-----+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.add_total_footer(3, &[0]).unwrap();
+        assert_eq!(table[1][0].get_content(), "1234567.5");
+    }
 
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
+    #[test]
+    fn cell_precision() {
+        use format::{NumberFormat, NumberLocale};
+        let mut table = table!(["1.2345", "1234.5"], ["2.5", "0.125"], ["n/a", "3"]);
+        table.set_column_number_format(0, NumberFormat::new().precision(1));
+        table.set_column_number_format(1, NumberFormat::new().locale(NumberLocale::european()));
+        assert_eq!(table.set_cell_precision(1, 0, 3), Ok(()));
+        assert_eq!(table.set_cell_precision(0, 1, 0), Ok(()));
+        assert_eq!(table.set_cell_precision(3, 0, 3), Err(TableError::RowOutOfBounds(3)));
+        assert_eq!(table.set_cell_precision(0, 2, 3), Err(TableError::ColumnOutOfBounds(2)));
+        let out = "\
++-------+-------+
+| 1.2   | 1.234 |
++-------+-------+
+| 2.500 | 0,125 |
++-------+-------+
+| n/a   | 3     |
++-------+-------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(table[1][0].get_content(), "2.5");
 
+        let mut table = table!([H2->"0.5", "1.25"], ["x", "y", "2"]);
+        assert_eq!(table.set_cell_precision(0, 2, 1), Ok(()));
+        assert_eq!(table.set_cell_precision(1, 2, 2), Ok(()));
+        assert_eq!(table.set_cell_precision(0, 3, 1), Err(TableError::ColumnOutOfBounds(3)));
+        assert_eq!(table[0][1].get_precision(), Some(1));
         let out = "\
-\u{0020}t1   t2      t3 \n\
-\u{0020}a    bc      def \n\
-\u{0020}def  newval  a \n\
++---+---+------+
+| 0.5   | 1.2  |
++-------+------+
+| x | y | 2.00 |
++---+---+------+
 ";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n", "\n"));
         assert_eq!(out, table.to_string().replace("\r\n", "\n"));
-        assert_eq!(3, table.print(&mut StringWriter::new()).unwrap());
     }
 
     #[test]
-    fn padding() {
-        let mut table = Table::new();
-        let mut format = *FORMAT_DEFAULT;
-        format.padding(2, 2);
-        table.set_format(format);
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        assert_eq!(table[1][1].get_content(), "bc");
-
-        table[1][1] = Cell::new("newval");
-        assert_eq!(table[1][1].get_content(), "newval");
+    fn sortable_indicator() {
+        let mut table = table!(["b", "1", "x"], ["a", "2", "y"]);
+        table.set_titles(crate::row!["name", "n", "z"]);
+        table.set_sortable_indicator(0, format::SortState::Asc);
+        table.set_sortable_indicator(1, format::SortState::Desc);
+        assert_eq!(table.get_sortable_indicator(1), format::SortState::Desc);
+        assert_eq!(table.get_sortable_indicator(2), format::SortState::None);
+        let out = "\
++--------+-----+---+
+| name ▲ | n ▼ | z |
++========+=====+===+
+| b      | 1   | x |
++--------+-----+---+
+| a      | 2   | y |
++--------+-----+---+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.set_sortable_indicator(0, format::SortState::None);
+        table.set_sortable_indicator(1, format::SortState::None);
+        assert!(table.to_string().contains("| name | n | z |"));
+    }
 
+    #[test]
+    fn column_valign() {
+        let mut table = table!(["top", "middle", "bottom", "a\nb\nc\nd"]);
+        table.set_column_valign(1, format::VAlign::Middle);
+        table.set_column_valign(2, format::VAlign::Bottom);
+        assert_eq!(table.get_column_valign(0), format::VAlign::Top);
+        assert_eq!(table.get_column_valign(2), format::VAlign::Bottom);
         let out = "\
-+-------+----------+-------+
-|  t1   |  t2      |  t3   |
-+=======+==========+=======+
-|  a    |  bc      |  def  |
-+-------+----------+-------+
-|  def  |  newval  |  a    |
-+-------+----------+-------+
++-----+--------+--------+---+
+| top |        |        | a |
+|     | middle |        | b |
+|     |        |        | c |
+|     |        | bottom | d |
++-----+--------+--------+---+
 ";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n", "\n"));
         assert_eq!(out, table.to_string().replace("\r\n", "\n"));
-        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
     }
 
     #[test]
-    fn indent() {
-        let mut table = Table::new();
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def"), Cell::new("bc"), Cell::new("a")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        table.get_format().indent(8);
-        let out = "        +-----+----+-----+
-        | t1  | t2 | t3  |
-        +=====+====+=====+
-        | a   | bc | def |
-        +-----+----+-----+
-        | def | bc | a   |
-        +-----+----+-----+
+    fn column_merge_vertical() {
+        let mut table = table!(["fruit", "apple", "1"], ["fruit", "pear", "1"], ["veg", "leek", "1"], ["fruit", "fig", "2"]);
+        table.set_column_merge_vertical(0, true);
+        table.set_column_merge_vertical(2, true);
+        assert!(table.get_column_merge_vertical(0));
+        assert!(!table.get_column_merge_vertical(1));
+        let out = "\
++-------+-------+---+
+| fruit | apple | 1 |
++-------+-------+---+
+|       | pear  |   |
++-------+-------+---+
+| veg   | leek  |   |
++-------+-------+---+
+| fruit | fig   | 2 |
++-------+-------+---+
 ";
-        assert_eq!(table.to_string().replace("\r\n", "\n"), out);
-        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(table[1][0].get_content(), "fruit");
+        // A slice shows the first value of its rows
+        assert!(table.slice(1..).to_string().contains("| fruit | pear | 1 |"));
+        table.set_column_merge_vertical(0, false);
+        table.set_column_merge_vertical(2, false);
+        assert!(table.to_string().contains("| fruit | pear  | 1 |"));
     }
 
     #[test]
-    fn slices() {
-        let mut table = Table::new();
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
-        table.add_row(Row::new(vec![Cell::new("0"), Cell::new("0"), Cell::new("0")]));
-        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
-        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
-        table.add_row(Row::new(vec![Cell::new("3"), Cell::new("3"), Cell::new("3")]));
-        table.add_row(Row::new(vec![Cell::new("4"), Cell::new("4"), Cell::new("4")]));
-        table.add_row(Row::new(vec![Cell::new("5"), Cell::new("5"), Cell::new("5")]));
+    fn column_empty_default() {
+        let mut table = table!(["a", "", " "], ["", "b"]);
+        table.set_titles(Row::from(vec!["t1", "", "t3"]));
+        table.set_column_empty_default(1, "—".to_string());
+        table.set_column_empty_default(2, "n/a".to_string());
+        assert_eq!(table.get_column_empty_default(1), Some("—"));
+        assert_eq!(table.get_column_empty_default(0), None);
         let out = "\
-+----+----+----+
-| t1 | t2 | t3 |
-+====+====+====+
-| 1  | 1  | 1  |
-+----+----+----+
-| 2  | 2  | 2  |
-+----+----+----+
-| 3  | 3  | 3  |
-+----+----+----+
++----+---+-----+
+| t1 |   | t3  |
++====+===+=====+
+| a  | — | n/a |
++----+---+-----+
+|    | b | n/a |
++----+---+-----+
 ";
-        let slice = table.slice(..);
-        let slice = slice.slice(1..);
-        let slice = slice.slice(..3);
-        assert_eq!(out, slice.to_string().replace("\r\n", "\n"));
-        assert_eq!(9, slice.print(&mut StringWriter::new()).unwrap());
-        assert_eq!(out, table.slice(1..4).to_string().replace("\r\n", "\n"));
-        assert_eq!(9, table.slice(1..4).print(&mut StringWriter::new()).unwrap());
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(table[0][1].get_content(), "");
+        assert_eq!(table[1].len(), 2);
+        table.unset_column_empty_default(1);
+        table.unset_column_empty_default(2);
+        assert_eq!(table.get_column_empty_default(1), None);
+        assert_eq!(table.to_string().lines().nth(3), Some("| a  |   |    |"));
     }
 
     #[test]
-    fn test_unicode_separators() {
-        let mut table = Table::new();
-        table.set_format(*FORMAT_BOX_CHARS);
-        table.add_row(Row::new(vec![Cell::new("1"), Cell::new("1"), Cell::new("1")]));
-        table.add_row(Row::new(vec![Cell::new("2"), Cell::new("2"), Cell::new("2")]));
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2"), Cell::new("t3")]));
+    fn absolute_max_width() {
+        let mut table = table!(["foobarbaz", "a"], ["qux", "bc"]);
+        table.set_titles(Row::from(vec!["Title", "T"]));
+        assert_eq!(table.get_absolute_max_width(), None);
+        table.set_absolute_max_width(Some(4));
+        assert_eq!(table.get_absolute_max_width(), Some(4));
         let out = "\
-┌────┬────┬────┐
-│ t1 │ t2 │ t3 │
-├────┼────┼────┤
-│ 1  │ 1  │ 1  │
-├────┼────┼────┤
-│ 2  │ 2  │ 2  │
-└────┴────┴────┘
++------+----+
+| Titl | T  |
++======+====+
+| foob | a  |
++------+----+
+| qux  | bc |
++------+----+
 ";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n", "\n"));
         assert_eq!(out, table.to_string().replace("\r\n", "\n"));
-        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+        table.set_absolute_max_width(None);
+        assert_eq!(table.to_string().lines().next(), Some("+-----------+----+"));
+
+        let mut table = table!(["\u{1b}[31mfoobarbaz\u{1b}[0m", "a"]);
+        table.set_absolute_max_width(Some(4));
+        assert_eq!(table.to_string().lines().nth(1), Some("| \u{1b}[31mfoob\u{1b}[0m | a |"));
     }
 
     #[test]
-    fn test_readme_format() {
+    fn print_many() {
+        let mut t1 = table!(["a", "bb"], ["ccc", "d"]);
+        t1.set_titles(Row::from(vec!["x", "y"]));
+        let mut t2 = table!(["eeee", "f"]);
+        t2.set_titles(Row::from(vec!["x", "y"]));
+        let mut out = Vec::new();
+        Table::print_many(&[&t1, &t2], &mut out).unwrap();
+        let expected = "\
++------+----+
+| x    | y  |
++======+====+
+| a    | bb |
++------+----+
+| ccc  | d  |
++------+----+
+| eeee | f  |
++------+----+
+";
+        assert_eq!(expected, String::from_utf8(out).unwrap().replace("\r\n", "\n"));
 
-        // The below is lifted from the README
+        let mut out = Vec::new();
+        assert!(Table::print_many(&[], &mut out).is_ok());
+        assert!(out.is_empty());
 
-        let mut table = Table::new();
-        let format = format::FormatBuilder::new()
-            .column_separator('|')
-            .borders('|')
-            .separators(&[format::LinePosition::Top,
-                        format::LinePosition::Bottom],
-                        format::LineSeparator::new('-', '+', '+', '+'))
-            .padding(1, 1)
-            .build();
-        table.set_format(format);
+        t2.set_titles(Row::from(vec!["x", "z"]));
+        let err = Table::print_many(&[&t1, &t2], &mut out).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    }
 
-        table.set_titles(Row::new(vec![Cell::new("Title 1"), Cell::new("Title 2")]));
-        table.add_row(Row::new(vec![Cell::new("Value 1"), Cell::new("Value 2")]));
-        table.add_row(Row::new(vec![Cell::new("Value three"), Cell::new("Value four")]));
+    #[test]
+    fn print_many_displays_rows() {
+        let mut table = table!(["a", "1234567"]);
+        table.set_titles(Row::from(vec!["Name", "Size"]));
+        table.set_column_number_format(1, format::NumberFormat::new().compact(1));
+        table.set_sortable_indicator(1, format::SortState::Asc);
+        table.set_column_icon(0, "#".to_string());
+        let mut out = Vec::new();
+        Table::print_many(&[&table], &mut out).unwrap();
+        assert_eq!(table.to_string(), String::from_utf8(out).unwrap());
+        assert!(table.to_string().contains("| # Name | Size ▲ |"));
+    }
 
+    #[test]
+    fn theme() {
+        let mut table = table!(["a", "bc"], ["def", "g"]);
+        table.set_titles(Row::from(vec!["t1", "t2"]));
+        table.apply_theme(&format::Theme::markdown_like().column_alignment(1, Alignment::RIGHT));
         let out = "\
-+-------------+------------+
-| Title 1     | Title 2    |
-| Value 1     | Value 2    |
-| Value three | Value four |
-+-------------+------------+
+| t1  | t2 |
+|-----|----|
+| a   | bc |
+| def |  g |
 ";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        assert_eq!(table.theme(), format::Theme::markdown_like().column_alignment(1, Alignment::RIGHT));
 
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n","\n"));
-        assert_eq!(out, table.to_string().replace("\r\n","\n"));
-        assert_eq!(5, table.print(&mut StringWriter::new()).unwrap());
+        let mut other = Table::new();
+        other.apply_theme(&table.theme());
+        assert_eq!(other.get_column_alignment(1), None);
+        other.add_row(Row::from(vec!["a", "b"]));
+        assert_eq!(other.get_column_alignment(1), Some(Alignment::RIGHT));
+        assert_eq!(*other.get_format(), *table.get_format());
+
+        table.apply_theme(&format::Theme::ascii());
+        assert_eq!(*table.get_format(), *FORMAT_DEFAULT);
+        assert_eq!(table.get_column_alignment(1), Some(Alignment::LEFT));
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn test_readme_format_with_title() {
-        let mut table = Table::new();
-        table.set_format(*format::consts::FORMAT_NO_LINESEP_WITH_TITLE);
+    fn format_from_map() {
+        use serde::de::value::{Error, MapDeserializer, StrDeserializer};
+        use serde::de::{Deserialize, IntoDeserializer};
 
-        table.set_titles(Row::new(vec![Cell::new("Title 1"), Cell::new("Title 2")]));
-        table.add_row(Row::new(vec![Cell::new("Value 1"), Cell::new("Value 2")]));
-        table.add_row(Row::new(vec![Cell::new("Value three"), Cell::new("Value four")]));
+        let align = |name| Alignment::deserialize(StrDeserializer::<Error>::new(name));
+        assert_eq!(align("RIGHT"), Ok(Alignment::RIGHT));
+        assert!(align("right").is_err());
 
-        let out = "\
-+-------------+------------+
-| Title 1     | Title 2    |
-+-------------+------------+
-| Value 1     | Value 2    |
-| Value three | Value four |
-+-------------+------------+
-";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n","\n"));
-        assert_eq!(out, table.to_string().replace("\r\n","\n"));
-        assert_eq!(6, table.print(&mut StringWriter::new()).unwrap());
+        let fields = vec![("header_sep", "Blank".into_deserializer()), ("h_overflow", "WrapColumns".into_deserializer())];
+        let format = format::TableFormat::deserialize(MapDeserializer::<_, Error>::new(fields.into_iter())).unwrap();
+        let mut expected = format::TableFormat::new();
+        expected.header_separator(format::HeaderSeparator::Blank);
+        expected.horizontal_overflow(format::HOverflow::WrapColumns);
+        assert_eq!(format, expected);
     }
 
     #[test]
-    fn test_horizontal_span() {
-        let mut table = Table::new();
-        table.set_titles(Row::new(vec![Cell::new("t1"), Cell::new("t2").with_hspan(2)]));
-        table.add_row(Row::new(vec![Cell::new("a"), Cell::new("bc"), Cell::new("def")]));
-        table.add_row(Row::new(vec![Cell::new("def").style_spec("H02c"), Cell::new("a")]));
+    fn anchor_alignment() {
+        let mut table = table!(["user: root", "12 ms"], ["uid: 0", "1500 ms"], ["shell: /bin/sh", "n/a"]);
+        table.set_titles(Row::from(vec!["Entry", "Time"]));
+        table.set_column_anchor(0, ":".to_string());
+        table.set_column_anchor(1, format!(" {}", "ms"));
+        assert_eq!(table.get_column_anchor(1), Some(" ms"));
+        assert_eq!(table.get_column_alignment(1), Some(Alignment::LEFT));
         let out = "\
-+----+----+-----+
-| t1 | t2       |
-+====+====+=====+
-| a  | bc | def |
-+----+----+-----+
-|   def   | a   |
-+----+----+-----+
++----------------+---------+
+| Entry          | Time    |
++================+=========+
+|  user: root    |   12 ms |
++----------------+---------+
+|   uid: 0       | 1500 ms |
++----------------+---------+
+| shell: /bin/sh |  n/a    |
++----------------+---------+
 ";
-        println!("{}", out);
-        println!("____");
-        println!("{}", table.to_string().replace("\r\n","\n"));
-        assert_eq!(out, table.to_string().replace("\r\n","\n"));
-        assert_eq!(7, table.print(&mut StringWriter::new()).unwrap());
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+
+        table[1][0].align(Alignment::RIGHT);
+        table[2][1].set_anchor("/".to_string());
+        table.insert_column(0, "", "").unwrap();
+        assert_eq!(table.get_column_anchor(0), None);
+        assert_eq!(table.get_column_anchor(1), Some(":"));
+        table.retain_columns(|i, _| i > 0);
+        let out = "\
++----------------+---------+
+| Entry          | Time    |
++================+=========+
+|  user: root    |   12 ms |
++----------------+---------+
+|         uid: 0 | 1500 ms |
++----------------+---------+
+| shell: /bin/sh |    n/a  |
++----------------+---------+
+";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+        table.unset_column_anchor(1);
+        assert_eq!(table.get_column_anchor(1), None);
     }
 
     #[test]
@@ -1023,6 +4810,123 @@ mod tests {
         assert_eq!(writer.as_string().replace("\r\n", "\n"), out);
     }
 
+    #[test]
+    fn table_html_annotation() {
+        let mut table = table!(["a", "1.5"]);
+        table.set_cell_annotation(0, 1, "mean of \"x\"".to_string()).unwrap();
+        assert_eq!(table.set_cell_annotation(0, 2, String::new()), Err(TableError::ColumnOutOfBounds(2)));
+        assert_eq!(table.set_cell_annotation(1, 0, String::new()), Err(TableError::RowOutOfBounds(1)));
+        let out = "\
+<table>\
+<tr><td style=\"text-align: left;\">a</td><td title=\"mean of &quot;x&quot;\" style=\"text-align: left;\">1.5</td></tr>\
+</table>";
+        let mut writer = StringWriter::new();
+        table.print_html(&mut writer).unwrap();
+        assert_eq!(writer.as_string(), out);
+        assert_eq!(table.to_string(), table!(["a", "1.5"]).to_string());
+
+        let mut table = table!([H2->"wide", "1.23456", "c"]);
+        table.set_cell_annotation(0, 2, "note".to_string()).unwrap();
+        table.set_cell_annotation(0, 1, "span".to_string()).unwrap();
+        assert_eq!(table[0][0].get_annotation(), Some("span"));
+        assert_eq!(table[0][1].get_annotation(), Some("note"));
+        assert_eq!(table[0][2].get_annotation(), None);
+        assert_eq!(table.set_cell_annotation(0, 4, String::new()), Err(TableError::ColumnOutOfBounds(4)));
+    }
+
+    #[test]
+    fn line_hook() {
+        let table = table!(["a", "b"], ["c", "d"]);
+        let mut out = StringWriter::new();
+        table.print_with_line_hook(&mut out, |i, line| format!("# {} {}", i, line)).unwrap();
+        let expected = "\
+# 0 +---+---+
+# 1 | a | b |
+# 2 +---+---+
+# 3 | c | d |
+# 4 +---+---+
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+
+        let mut seen = Vec::new();
+        table.print_with_line_hook(&mut StringWriter::new(), |_, line| {
+            seen.push(line.to_string());
+            String::new()
+        }).unwrap();
+        assert_eq!(seen.join("\n") + "\n", table.to_string().replace("\r\n", "\n"));
+    }
+
+    #[test]
+    fn markdown() {
+        let mut table = table!(["a", "1.5", "x|y"], ["long cell", "10", "two\nlines"]);
+        table.set_titles(Row::from(vec!["name", "value"]));
+        table.set_column_alignment(1, Alignment::RIGHT);
+        table.set_column_alignment(2, Alignment::CENTER);
+        let mut out = StringWriter::new();
+        table.print_markdown(&mut out, false).unwrap();
+        let expected = "\
+| name | value |  |
+| --- | --: | :-: |
+| a | 1.5 | x\\|y |
+| long cell | 10 | two<br>lines |
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+
+        let mut out = StringWriter::new();
+        table.print_markdown(&mut out, true).unwrap();
+        let expected = "\
+| name      | value |              |
+| --------- | ----: | :----------: |
+| a         |   1.5 |     x\\|y     |
+| long cell |    10 | two<br>lines |
+";
+        assert_eq!(out.as_string().replace("\r\n", "\n"), expected);
+
+        // The Markdown output doesn't depend on separators, borders, padding or indentation
+        table.set_format(format::FormatBuilder::new()
+            .column_separator('#')
+            .borders('*')
+            .separators(&[format::LinePosition::Top, format::LinePosition::Title],
+                        format::LineSeparator::new('=', '+', '+', '+'))
+            .padding(3, 2)
+            .indent(4)
+            .build());
+        let mut custom = StringWriter::new();
+        table.print_markdown(&mut custom, true).unwrap();
+        assert_eq!(custom.as_string(), out.as_string());
+
+        let mut table = table!(["a", "b"]);
+        table.get_mut_row(0).unwrap().get_mut_cell(0).unwrap().set_hspan(2);
+        table.get_mut_row(0).unwrap().remove_cell(1);
+        let mut out = StringWriter::new();
+        table.print_markdown(&mut out, true).unwrap();
+        assert_eq!(out.as_string().replace("\r\n", "\n"), "|     |     |\n| --- | --- |\n| a   |     |\n");
+
+        let mut out = StringWriter::new();
+        Table::new().print_markdown(&mut out, true).unwrap();
+        assert_eq!(out.as_string(), "");
+    }
+
+    #[test]
+    fn table_html_ignores_format() {
+        let mut table = table!(["a", "bc", "def"], ["def", "bc", "a"]);
+        table.set_titles(Row::from(vec!["t1", "t2", "t3"]));
+        let mut expected = StringWriter::new();
+        table.print_html(&mut expected).unwrap();
+
+        let mut format = *FORMAT_BOX_CHARS;
+        format.padding(3, 2);
+        format.indent(4);
+        format.max_width(Some(5));
+        format.horizontal_overflow(format::HOverflow::Indicator);
+        format.separator(format::LinePosition::Intern, format::LineSeparator::new('~', '#', '<', '>'));
+        table.set_format(format);
+        table.set_absolute_max_width(Some(1));
+        let mut writer = StringWriter::new();
+        table.print_html(&mut writer).unwrap();
+        assert_eq!(writer.as_string(), expected.as_string());
+    }
+
     #[test]
     fn table_html_colors() {
         let mut table = Table::new();
@@ -31,7 +31,7 @@ fn main() {
     }
     table.printstd();
     println!("Modified : ");
-    table.set_element("new_foo", 2, 1).unwrap();
+    table.set_cell(1, 2, "new_foo").unwrap();
     table.printstd();
     // table.get_format().indent(8);
 
@@ -1,5 +1,6 @@
 //! This module contains definition of table rows stuff
 use std::io::{Write, Error};
+use std::hash::{Hash, Hasher};
 use std::iter::FromIterator;
 use std::slice::{Iter, IterMut};
 // use std::vec::IntoIter;
@@ -7,20 +8,22 @@ use std::ops::{Index, IndexMut};
 
 use super::Terminal;
 
-use super::utils::NEWLINE;
+use super::utils::{NEWLINE, Background};
 use super::Cell;
-use super::format::{TableFormat, ColumnPosition};
+use super::format::{TableFormat, ColumnPosition, Alignment, VAlign};
 
-/// Represent a table row made of cells
-#[derive(Clone, Debug, Hash, PartialEq, Eq)]
+/// Represent a table row made of cells.
+/// Rows are compared and hashed by their cells only, the blank line after them is ignored
+#[derive(Clone, Debug, Eq)]
 pub struct Row {
     cells: Vec<Cell>,
+    blank_after: bool,
 }
 
 impl Row {
     /// Create a new `Row` backed with `cells` vector
     pub fn new(cells: Vec<Cell>) -> Row {
-        Row { cells }
+        Row { cells, blank_after: false }
     }
 
     /// Create an row of length `size`, with empty strings stored
@@ -47,6 +50,18 @@ impl Row {
         self.cells.is_empty()
     }
 
+    /// Print a blank line, with borders and column separators, between this row and the next one
+    /// when printed in a table. This spacer is not a row, and stays after this row when rows are
+    /// sorted or moved
+    pub fn set_blank_after(&mut self, blank: bool) {
+        self.blank_after = blank;
+    }
+
+    /// Check if a blank line is printed between this row and the next one
+    pub fn is_blank_after(&self) -> bool {
+        self.blank_after
+    }
+
     /// Get the height of this row
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     fn get_height(&self) -> usize {
@@ -86,6 +101,47 @@ impl Row {
         0
     }
 
+    /// Build a new row made of the cells covering grid columns `start` to `end` (excluded).
+    /// Horizontal spans crossing the bounds are truncated.
+    pub (crate) fn project(&self, start: usize, end: usize) -> Row {
+        let mut cells = Vec::new();
+        let mut i = 0;
+        for c in &self.cells {
+            let (from, to) = (i.max(start), (i + c.get_hspan()).min(end));
+            if from < to {
+                cells.push(c.clone().with_hspan(to - from));
+            }
+            i += c.get_hspan();
+        }
+        let mut row = Row::new(cells);
+        row.blank_after = self.blank_after;
+        row
+    }
+
+    /// Get the cell covering the grid column `column`, taking horizontal spans into account
+    pub (crate) fn get_cell_at_column(&self, column: usize) -> Option<&Cell> {
+        let mut i = 0;
+        for c in &self.cells {
+            i += c.get_hspan();
+            if i > column {
+                return Some(c);
+            }
+        }
+        None
+    }
+
+    /// Get the mutable cell covering grid column `column`, taking horizontal spans into account
+    pub (crate) fn get_mut_cell_at_column(&mut self, column: usize) -> Option<&mut Cell> {
+        let mut i = 0;
+        for c in &mut self.cells {
+            i += c.get_hspan();
+            if i > column {
+                return Some(c);
+            }
+        }
+        None
+    }
+
     /// Get the cell at index `idx`
     pub fn get_cell(&self, idx: usize) -> Option<&Cell> {
         self.cells.get(idx)
@@ -120,6 +176,94 @@ impl Row {
         }
     }
 
+    /// Return a row of empty cells spanning the same columns as the cells of this row
+    pub (crate) fn blanked(&self) -> Row {
+        Row::new(self.cells.iter().map(|c| Cell::default().with_hspan(c.get_hspan())).collect())
+    }
+
+    /// Return a copy of the row whose foreground colors are readable on `background`
+    pub (crate) fn adapted_to(&self, background: Background) -> Row {
+        Row::new(self.cells.iter().map(|c| c.adapted_to(background)).collect())
+    }
+
+    /// Insert `cell` so that it starts at grid column `column`, taking horizontal spans into account.
+    /// If the row is shorter, it is first padded with empty cells
+    pub (crate) fn insert_cell_at_column(&mut self, column: usize, cell: Cell) {
+        let mut i = 0;
+        let mut col = 0;
+        while i < self.cells.len() && col < column {
+            col += self.cells[i].get_hspan();
+            i += 1;
+        }
+        while col < column {
+            self.cells.push(Cell::default());
+            col += 1;
+            i += 1;
+        }
+        self.cells.insert(i, cell);
+    }
+
+    /// Remove grid column `column` from the row, taking horizontal spans into account.
+    /// A cell spanning several columns is shrunk instead of being removed
+    pub (crate) fn remove_cell_at_column(&mut self, column: usize) {
+        let mut col = 0;
+        for i in 0..self.cells.len() {
+            let hspan = self.cells[i].get_hspan();
+            if col + hspan > column {
+                if hspan > 1 {
+                    self.cells[i].set_hspan(hspan - 1);
+                } else {
+                    self.cells.remove(i);
+                }
+                return;
+            }
+            col += hspan;
+        }
+    }
+
+    /// Make the cell at grid column `column` span `span` columns, in place. Cells covered by a
+    /// wider span are removed, and empty cells fill the columns uncovered by a narrower one.
+    /// A cell spanning over `column` from a previous column is shrunk to end before it, and
+    /// a row shorter than `column` is first padded with empty cells
+    pub (crate) fn span_at_column(&mut self, column: usize, span: usize) {
+        let mut i = 0;
+        let mut col = 0;
+        while i < self.cells.len() && col + self.cells[i].get_hspan() <= column {
+            col += self.cells[i].get_hspan();
+            i += 1;
+        }
+        if i == self.cells.len() {
+            while col <= column {
+                self.cells.push(Cell::default());
+                col += 1;
+            }
+            i = self.cells.len() - 1;
+        } else if col < column {
+            let hspan = self.cells[i].get_hspan();
+            self.cells[i].set_hspan(column - col);
+            i += 1;
+            self.cells.insert(i, Cell::default().with_hspan(col + hspan - column));
+        }
+        let old = self.cells[i].get_hspan();
+        self.cells[i].set_hspan(span);
+        if span < old {
+            for _ in span..old {
+                self.cells.insert(i + 1, Cell::default());
+            }
+        }
+        let mut extra = span.saturating_sub(old);
+        while extra > 0 && i + 1 < self.cells.len() {
+            let hspan = self.cells[i + 1].get_hspan();
+            if hspan <= extra {
+                self.cells.remove(i + 1);
+                extra -= hspan;
+            } else {
+                self.cells[i + 1].set_hspan(hspan - extra);
+                extra = 0;
+            }
+        }
+    }
+
     /// Remove the cell at position `index`. Silently skip if this cell does not exist
     pub fn remove_cell(&mut self, index: usize) {
         if index < self.cells.len() {
@@ -138,13 +282,17 @@ impl Row {
     }
 
     /// Internal only
+    #[allow(clippy::too_many_arguments)]
     fn __print<T: Write + ?Sized, F>(&self,
                                      out: &mut T,
                                      format: &TableFormat,
                                      col_width: &[usize],
+                                     col_align: &[Alignment],
+                                     col_anchor: &[usize],
+                                     col_valign: &[VAlign],
                                      f: F)
                                      -> Result<usize, Error>
-        where F: Fn(&Cell, &mut T, usize, usize, bool) -> Result<(), Error>
+        where F: Fn(&Cell, &mut T, usize, usize, bool, Alignment, usize) -> Result<(), Error>
     {
         let height = self.get_height();
         for i in 0..height {
@@ -158,23 +306,42 @@ impl Row {
                 out.write_all(&vec![b' '; lp])?; // Left padding
                 // skip_r_fill skip filling the end of the last cell if there's no character
                 // delimiting the end of the table
-                let skip_r_fill = (j == col_width.len() - 1) &&
-                                  format.get_column_separator(ColumnPosition::Right).is_none();
+                let no_rborder = format.get_column_separator(ColumnPosition::Right).is_none();
+                let skip_r_fill = (j == col_width.len() - 1) && no_rborder;
+                // Without padding of the last column, lines end right after the last cell reaching it
+                let ragged = !format.get_pad_last_column() && no_rborder &&
+                             j+hspan+self.get_cell(j).map_or(1, Cell::get_hspan) >= col_width.len();
+                let skip_r_fill = skip_r_fill || ragged;
+                let align = col_align.get(j+hspan).cloned().unwrap_or(Alignment::LEFT);
+                let anchor = col_anchor.get(j+hspan).cloned().unwrap_or(0);
+                let valign = col_valign.get(j+hspan).cloned().unwrap_or(VAlign::Top);
                 match self.get_cell(j) {
                     Some(c) => {
-                        // In case of horizontal spanning, width is the sum of all spanned columns' width
-                        let mut w = col_width[j+hspan..j+hspan+c.get_hspan()].iter().sum();
-                        let real_span = c.get_hspan()-1;
+                        // Index of the cell's line to print, blank lines being added according to vertical alignment
+                        let offset = match valign {
+                            VAlign::Top => 0,
+                            VAlign::Middle => (height - c.get_height()) / 2,
+                            VAlign::Bottom => height - c.get_height(),
+                        };
+                        let idx = i.checked_sub(offset).unwrap_or_else(|| c.get_height());
+                        // In case of horizontal spanning, width is the sum of all spanned columns' width.
+                        // Spans crossing the last column are truncated
+                        let span = c.get_hspan().min(col_width.len() - (j+hspan));
+                        let mut w = col_width[j+hspan..j+hspan+span].iter().sum();
+                        let real_span = span-1;
                         w += real_span * (lp + rp) + real_span * format.get_column_separator(ColumnPosition::Intern).map(|_| 1).unwrap_or_default();
                         // Print cell content
-                        f(c, out, i, w, skip_r_fill)?;
+                        let anchor = if c.get_hspan() == 1 { anchor } else { 0 };
+                        f(c, out, idx, w, skip_r_fill, align, anchor)?;
                         hspan += real_span; // Add span to offset
                     },
-                    None => f(&Cell::default(), out, i, col_width[j+hspan], skip_r_fill)?,
+                    None => f(&Cell::default(), out, i, col_width[j+hspan], skip_r_fill, align, anchor)?,
                 };
-                out.write_all(&vec![b' '; rp])?; // Right padding
+                if !ragged {
+                    out.write_all(&vec![b' '; rp])?; // Right padding
+                }
                 if j+hspan < col_width.len() - 1 {
-                    format.print_column_separator(out, ColumnPosition::Intern)?;
+                    format.print_internal_separator(out, j+hspan+1)?;
                 }
                 j+=1;
             }
@@ -184,44 +351,83 @@ impl Row {
         Ok(height)
     }
 
-    /// Print the row to `out`, with `separator` as column separator, and `col_width`
-    /// specifying the width of each columns. Returns the number of printed lines
+    /// Print the row to `out`, with `separator` as column separator, `col_width`
+    /// specifying the width of each columns, `col_align` their default alignment,
+    /// `col_anchor` the position of the anchor of anchored cells
+    /// and `col_valign` their vertical alignment.
+    /// Returns the number of printed lines
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     pub (crate) fn print<T: Write + ?Sized>(&self,
                                     out: &mut T,
                                     format: &TableFormat,
-                                    col_width: &[usize])
+                                    col_width: &[usize],
+                                    col_align: &[Alignment],
+                                    col_anchor: &[usize],
+                                    col_valign: &[VAlign])
                                     -> Result<usize, Error> {
-        self.__print(out, format, col_width, Cell::print)
+        self.__print(out, format, col_width, col_align, col_anchor, col_valign, Cell::print)
     }
 
-    /// Print the row to terminal `out`, with `separator` as column separator, and `col_width`
-    /// specifying the width of each columns. Apply style when needed. returns the number of printed lines
+    /// Print the row to terminal `out`, with `separator` as column separator, `col_width`
+    /// specifying the width of each columns, `col_align` their default alignment,
+    /// `col_anchor` the position of the anchor of anchored cells
+    /// and `col_valign` their vertical alignment.
+    /// Apply style when needed, including the format's continuation style on
+    /// lines following the first one of multiline cells. returns the number of printed lines
     // #[deprecated(since="0.8.0", note="Will become private in future release. See [issue #87](https://github.com/phsym/prettytable-rs/issues/87)")]
     pub (crate) fn print_term<T: Terminal + ?Sized>(&self,
                                             out: &mut T,
                                             format: &TableFormat,
-                                            col_width: &[usize])
+                                            col_width: &[usize],
+                                            col_align: &[Alignment],
+                                            col_anchor: &[usize],
+                                            col_valign: &[VAlign])
                                             -> Result<usize, Error> {
-        self.__print(out, format, col_width, Cell::print_term)
+        let continuation = format.get_continuation_style();
+        self.__print(out, format, col_width, col_align, col_anchor, col_valign, |c, out, i, w, skip_r_fill, align, anchor| {
+            c.print_term(out, i, w, skip_r_fill, align, anchor, continuation)
+        })
     }
 
     /// Print the row in HTML format to `out`.
     ///
     /// If the row is has fewer columns than `col_num`, the row is padded with empty cells.
     pub fn print_html<T: Write + ?Sized>(&self, out: &mut T, col_num: usize) -> Result<(), Error> {
+        self.print_html_aligned(out, col_num, &[])
+    }
+
+    /// Print the row in HTML format to `out`, with `col_align` specifying
+    /// the default alignment of each column.
+    pub (crate) fn print_html_aligned<T: Write + ?Sized>(&self,
+                                                         out: &mut T,
+                                                         col_num: usize,
+                                                         col_align: &[Alignment])
+                                                         -> Result<(), Error> {
+        let align = |i: usize| col_align.get(i).cloned().unwrap_or(Alignment::LEFT);
         let mut printed_columns = 0;
         for cell in self.iter() {
-            printed_columns += cell.print_html(out)?;
+            printed_columns += cell.print_html_aligned(out, align(printed_columns))?;
         }
         // Pad with empty cells, if target width is not reached
-        for _ in 0..col_num - printed_columns {
-            Cell::default().print_html(out)?;
+        while printed_columns < col_num {
+            printed_columns += Cell::default().print_html_aligned(out, align(printed_columns))?;
         }
         Ok(())
     }
 }
 
+impl PartialEq for Row {
+    fn eq(&self, other: &Row) -> bool {
+        self.cells == other.cells
+    }
+}
+
+impl Hash for Row {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.cells.hash(state);
+    }
+}
+
 impl Default for Row {
     fn default() -> Row {
         Row::empty()
@@ -296,6 +502,7 @@ impl <S: ToString> Extend<S> for Row {
 
 /// This macro simplifies `Row` creation
 ///
+/// Elements can be of any type implementing `std::string::ToString`, and can be mixed.
 /// The syntax support style spec
 /// # Example
 /// ```
@@ -308,10 +515,13 @@ impl <S: ToString> Extend<S> for Row {
 /// let row2 = row![FrBybic => "Element 1", "Element 2", "Element 3"];
 /// // Create a row with first cell in blue, second one in red, and last one with default style
 /// let row3 = row![Fb->"blue", Fr->"red", "normal"];
+/// // Create a row from values of different types
+/// let row4 = row!["a", 1, true];
 /// // Do something with rows
 /// # drop(row1);
 /// # drop(row2);
 /// # drop(row3);
+/// # drop(row4);
 /// # }
 /// ```
 ///
@@ -394,4 +604,13 @@ mod tests {
         assert_eq!(row.get_cell(4).unwrap().get_content(), "B");
         assert_eq!(row.get_cell(5).unwrap().get_content(), "C");
     }
+
+    #[test]
+    fn row_macro() {
+        let row = crate::row!["a", 1, true, 2.5];
+        assert_eq!(row, Row::from(vec!["a", "1", "true", "2.5"]));
+        let row = crate::row![Fr -> 'c', 42];
+        assert_eq!(row.get_cell(0).unwrap().get_content(), "c");
+        assert_eq!(row.get_cell(1).unwrap().get_content(), "42");
+    }
 }
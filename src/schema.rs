@@ -0,0 +1,148 @@
+//! This module contains the column-first construction of tables
+
+use super::format::{Alignment, NumberFormat, TableFormat};
+use super::{Cell, Row, Table};
+
+/// Definition of a table column: its title, alignment, and optionally
+/// its fixed width and number format. See `TableSchema`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ColumnDef {
+    title: String,
+    align: Alignment,
+    width: Option<usize>,
+    number: Option<NumberFormat>,
+}
+
+impl ColumnDef {
+    /// Create a column titled `title`, whose cells are aligned with `align`
+    pub fn new(title: &str, align: Alignment) -> ColumnDef {
+        ColumnDef {
+            title: title.to_string(),
+            align,
+            width: None,
+            number: None,
+        }
+    }
+
+    /// Give the column a fixed width, see `Table::set_column_fixed`
+    pub fn width(mut self, width: usize) -> ColumnDef {
+        self.width = Some(width);
+        self
+    }
+
+    /// Display numbers of the column with `format`, see `Table::set_column_number_format`
+    pub fn number_format(mut self, format: NumberFormat) -> ColumnDef {
+        self.number = Some(format);
+        self
+    }
+
+    /// Get the title of the column
+    pub fn get_title(&self) -> &str {
+        &self.title
+    }
+
+    /// Get the alignment of the column
+    pub fn get_alignment(&self) -> Alignment {
+        self.align
+    }
+
+    /// Get the fixed width of the column, if any
+    pub fn get_width(&self) -> Option<usize> {
+        self.width
+    }
+
+    /// Get the number format of the column, if any
+    pub fn get_number_format(&self) -> Option<NumberFormat> {
+        self.number
+    }
+}
+
+/// A builder creating an empty table from the definitions of its columns,
+/// rather than configuring each column by index once titles are set.
+///
+/// # Example
+/// ```rust
+/// # use prettytable::TableSchema;
+/// # use prettytable::format::Alignment;
+/// let mut table = TableSchema::new()
+///     .column("Name", Alignment::LEFT)
+///     .column("Age", Alignment::RIGHT)
+///     .build();
+/// table.add_row(prettytable::row!["Alice", 31]);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TableSchema {
+    columns: Vec<ColumnDef>,
+    format: Option<TableFormat>,
+}
+
+impl TableSchema {
+    /// Create a schema without column
+    pub fn new() -> TableSchema {
+        TableSchema::default()
+    }
+
+    /// Append a column titled `title`, whose cells are aligned with `align`
+    pub fn column(self, title: &str, align: Alignment) -> TableSchema {
+        self.column_def(ColumnDef::new(title, align))
+    }
+
+    /// Append the column defined by `def`
+    pub fn column_def(mut self, def: ColumnDef) -> TableSchema {
+        self.columns.push(def);
+        self
+    }
+
+    /// Use `format` for the table instead of the default one
+    pub fn format(mut self, format: TableFormat) -> TableSchema {
+        self.format = Some(format);
+        self
+    }
+
+    /// Get the definitions of the columns
+    pub fn get_columns(&self) -> &[ColumnDef] {
+        &self.columns
+    }
+
+    /// Create an empty table with the titles and column settings of the schema
+    pub fn build(&self) -> Table {
+        let mut table = Table::new();
+        if let Some(format) = self.format {
+            table.set_format(format);
+        }
+        table.set_titles(Row::new(self.columns.iter().map(|c| Cell::new(&c.title)).collect()));
+        for (i, c) in self.columns.iter().enumerate() {
+            table.set_column_alignment(i, c.align);
+            if let Some(width) = c.width {
+                table.set_column_fixed(i, width);
+            }
+            if let Some(number) = c.number {
+                table.set_column_number_format(i, number);
+            }
+        }
+        table
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::consts::FORMAT_CLEAN;
+
+    #[test]
+    fn build() {
+        let schema = TableSchema::new()
+            .column("Name", Alignment::LEFT)
+            .column_def(ColumnDef::new("Size", Alignment::RIGHT).number_format(NumberFormat::new().compact(2)))
+            .column_def(ColumnDef::new("Comment", Alignment::LEFT).width(4))
+            .format(*FORMAT_CLEAN);
+        assert_eq!(schema.get_columns().len(), 3);
+        assert_eq!(schema.get_columns()[2].get_width(), Some(4));
+        let mut table = schema.build();
+        assert!(table.is_empty());
+        assert_eq!(table.get_column_alignment(1), Some(Alignment::RIGHT));
+        table.add_row(crate::row!["file", "123456", "too long"]);
+        let out = " Name  Size  Com› \n file  123k  too› \n";
+        assert_eq!(out, table.to_string().replace("\r\n", "\n"));
+    }
+}
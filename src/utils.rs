@@ -1,11 +1,16 @@
 //! Internal only utilities
+use std::collections::HashSet;
+use std::env;
 use std::fmt;
-use std::io::{Error, ErrorKind, Write};
+use std::hash::{Hash, Hasher};
+use std::io::{self, Error, ErrorKind, Write};
 use std::str;
+use std::sync::{Arc, Once};
 
-use unicode_width::UnicodeWidthStr;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 use super::format::Alignment;
+use super::color::{self, Color};
 
 #[cfg(any(not(windows), not(feature="win_crlf")))]
 pub static NEWLINE: &[u8] = b"\n";
@@ -48,6 +53,115 @@ impl Write for StringWriter {
     }
 }
 
+/// Writer forwarding everything to an inner writer, while counting the bytes written.
+/// After printing a table to it, `bytes_written` tells the size of the output
+///
+/// # Examples
+/// ```rust
+/// # #[macro_use] extern crate prettytable;
+/// use prettytable::CountingWriter;
+/// # fn main() {
+/// let table = table!(["a", "b"]);
+/// let mut out = CountingWriter::new(Vec::new());
+/// table.print(&mut out).unwrap();
+/// assert_eq!(out.bytes_written(), out.get_ref().len() as u64);
+/// # }
+/// ```
+#[derive(Debug, Default)]
+pub struct CountingWriter<W> {
+    inner: W,
+    count: u64,
+}
+
+impl<W: Write> CountingWriter<W> {
+    /// Wrap `inner`, with a count of 0 bytes written
+    pub fn new(inner: W) -> CountingWriter<W> {
+        CountingWriter { inner, count: 0 }
+    }
+
+    /// Get the number of bytes written so far to the inner writer
+    pub fn bytes_written(&self) -> u64 {
+        self.count
+    }
+
+    /// Get a reference to the inner writer
+    pub fn get_ref(&self) -> &W {
+        &self.inner
+    }
+
+    /// Get a mutable reference to the inner writer. Bytes written directly to it are not counted
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.inner
+    }
+
+    /// Unwrap the inner writer
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+impl<W: Write> Write for CountingWriter<W> {
+    fn write(&mut self, data: &[u8]) -> Result<usize, Error> {
+        let written = self.inner.write(data)?;
+        self.count += written as u64;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.inner.flush()
+    }
+}
+
+/// Set of shared strings, used to deduplicate the content of cells.
+/// It is only a cache, and is ignored when comparing or hashing.
+/// Entries are never removed: the set keeps every distinct value it has been given, even once
+/// no cell uses it anymore, for as long as the table owning it lives
+#[derive(Clone, Debug, Default)]
+pub struct Interner(HashSet<Arc<str>>);
+
+impl Interner {
+    /// Return a shared string equal to `s`, allocating it only the first time it is requested
+    pub fn intern(&mut self, s: &str) -> Arc<str> {
+        if let Some(shared) = self.0.get(s) {
+            return shared.clone();
+        }
+        let shared: Arc<str> = Arc::from(s);
+        self.0.insert(shared.clone());
+        shared
+    }
+}
+
+impl PartialEq for Interner {
+    fn eq(&self, _: &Interner) -> bool {
+        true
+    }
+}
+
+impl Eq for Interner {}
+
+impl Hash for Interner {
+    fn hash<H: Hasher>(&self, _: &mut H) {}
+}
+
+/// Maximum value of a column shown as bars. It is compared and hashed by its bits, so that
+/// tables remain `Eq` and `Hash`
+#[derive(Clone, Copy, Debug)]
+pub struct BarMax(pub f64);
+
+impl PartialEq for BarMax {
+    fn eq(&self, other: &BarMax) -> bool {
+        self.0.to_bits() == other.0.to_bits()
+    }
+}
+
+impl Eq for BarMax {}
+
+impl Hash for BarMax {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.0.to_bits().hash(state);
+    }
+}
+
 /// Align/fill a string and print it to `out`
 /// If `skip_right_fill` is set to `true`, then no space will be added after the string
 /// to complete alignment
@@ -76,6 +190,411 @@ pub fn print_align<T: Write + ?Sized>(out: &mut T,
     Ok(())
 }
 
+/// Return the display width of the part of `text` before the first occurrence of `anchor`,
+/// or the width of the whole text if it does not contain `anchor`
+pub fn anchor_offset(text: &str, anchor: &str) -> usize {
+    match text.find(anchor) {
+        Some(i) => display_width(&text[..i]),
+        None => display_width(text),
+    }
+}
+
+/// Byte offset, character and display width of each character of `text`. Like in
+/// `display_width`, the characters of ANSI escape sequences are zero width
+fn char_widths(text: &str) -> Vec<(usize, char, usize)> {
+    let mut chars: Vec<(usize, char, usize)> = text.char_indices()
+        .map(|(i, c)| (i, c, UnicodeWidthChar::width(c).unwrap_or(0)))
+        .collect();
+    let mut k = 0;
+    while k < chars.len() {
+        if chars[k].1 == '\u{1b}' && chars.get(k + 1).map_or(false, |&(_, c, _)| c == '[') {
+            while k < chars.len() {
+                chars[k].2 = 0;
+                k += 1;
+                if chars[k - 1].1 == 'm' {
+                    break;
+                }
+            }
+        } else {
+            k += 1;
+        }
+    }
+    chars
+}
+
+/// Return the ANSI escape sequences found in `text`, one after the other
+pub fn escape_sequences(text: &str) -> String {
+    let mut sequences = String::new();
+    let mut rest = text;
+    while let Some(i) = rest.find("\u{1b}[") {
+        let seq = &rest[i..];
+        let end = seq.find('m').map_or(seq.len(), |e| e + 1);
+        sequences.push_str(&seq[..end]);
+        rest = &seq[end..];
+    }
+    sequences
+}
+
+/// Return the longest prefix of `text` whose display width doesn't exceed `width`.
+/// Escape sequences don't count toward the width
+pub fn truncate(text: &str, width: usize) -> &str {
+    let mut current = 0;
+    for (i, _, w) in char_widths(text) {
+        current += w;
+        if current > width {
+            return &text[..i];
+        }
+    }
+    text
+}
+
+/// Return the longest suffix of `text` whose display width doesn't exceed `width`.
+/// Escape sequences don't count toward the width
+pub fn truncate_start(text: &str, width: usize) -> &str {
+    let mut current = 0;
+    for (i, c, w) in char_widths(text).into_iter().rev() {
+        current += w;
+        if current > width {
+            return &text[i + c.len_utf8()..];
+        }
+    }
+    text
+}
+
+/// Shorten `text` to at most `width` columns if it is wider, marking the clipped sides with `‹` and `›`.
+/// The end of left aligned text is clipped, the start of right aligned text, and both sides of centered text
+pub fn clip(text: &str, width: usize, align: Alignment) -> String {
+    let text_width = display_width(text);
+    if text_width <= width {
+        return text.to_string();
+    }
+    match align {
+        _ if width == 0 => String::new(),
+        Alignment::LEFT => format!("{}›", truncate(text, width - 1)),
+        Alignment::RIGHT => format!("‹{}", truncate_start(text, width - 1)),
+        Alignment::CENTER if width < 2 => "›".to_string(),
+        Alignment::CENTER => {
+            let keep = width - 2;
+            let end = truncate(text, keep + (text_width - keep) / 2);
+            format!("‹{}›", truncate_start(end, keep))
+        }
+    }
+}
+
+/// Share `budget` columns between columns whose natural widths are `natural`, proportionally
+/// to these widths. Every non empty column gets at least one column of width
+pub fn fit_proportional(natural: &[usize], budget: usize) -> Vec<usize> {
+    let total: usize = natural.iter().sum();
+    if total <= budget {
+        return natural.to_vec();
+    }
+    let mut widths: Vec<usize> = natural.iter().map(|&n| (n * budget / total).max(n.min(1))).collect();
+    // Give remaining width to columns which lost the most when rounding down
+    let mut order: Vec<usize> = (0..natural.len()).collect();
+    order.sort_by_key(|&i| std::cmp::Reverse(natural[i] * budget % total));
+    let mut left = budget.saturating_sub(widths.iter().sum());
+    for i in order {
+        if left == 0 {
+            break;
+        }
+        if widths[i] < natural[i] {
+            widths[i] += 1;
+            left -= 1;
+        }
+    }
+    widths
+}
+
+/// Share `budget` columns between columns, `lines` being every line of the cells of each column.
+/// Widths are chosen to minimize the total number of extra lines created by wrapping.
+/// Every non empty column gets at least one column of width
+pub fn fit_balanced<S: AsRef<str>>(lines: &[Vec<S>], budget: usize) -> Vec<usize> {
+    let natural: Vec<usize> = lines.iter()
+        .map(|l| l.iter().map(|s| display_width(s.as_ref())).max().unwrap_or(0))
+        .collect();
+    if natural.iter().sum::<usize>() <= budget {
+        return natural;
+    }
+    let min: usize = natural.iter().map(|&n| n.min(1)).sum();
+    if budget < min {
+        return natural.iter().map(|&n| n.min(1)).collect();
+    }
+    // best[b] is the lowest cost of the columns processed so far with a total width of `b`
+    let mut best: Vec<Option<usize>> = vec![None; budget + 1];
+    best[0] = Some(0);
+    let mut choices: Vec<Vec<usize>> = Vec::with_capacity(lines.len());
+    for (col, &n) in lines.iter().zip(&natural) {
+        let mut next = vec![None; budget + 1];
+        let mut choice = vec![0; budget + 1];
+        for w in n.min(1)..=n.min(budget) {
+            let cost: usize = col.iter().map(|l| wrap_text(l.as_ref(), w).len().max(1) - 1).sum();
+            for b in 0..=budget - w {
+                if let Some(c) = best[b] {
+                    if next[b + w].map(|nc| c + cost < nc).unwrap_or(true) {
+                        next[b + w] = Some(c + cost);
+                        choice[b + w] = w;
+                    }
+                }
+            }
+        }
+        best = next;
+        choices.push(choice);
+    }
+    // Among allocations with the lowest cost, prefer the widest
+    let mut b = (0..=budget).rev().min_by_key(|&b| best[b].unwrap_or(std::usize::MAX)).unwrap_or(0);
+    let mut widths = vec![0; lines.len()];
+    for (i, choice) in choices.iter().enumerate().rev() {
+        widths[i] = choice[b];
+        b -= choice[b];
+    }
+    widths
+}
+
+/// Render `ratio`, clamped between 0 and 1, as a bar of block characters
+/// `width` columns wide, using eighth blocks for the last partial column
+pub fn render_bar(ratio: f64, width: usize) -> String {
+    const PARTIAL: [char; 7] = ['▏', '▎', '▍', '▌', '▋', '▊', '▉'];
+    let ratio = if ratio > 0.0 { ratio.min(1.0) } else { 0.0 };
+    let eighths = (ratio * width as f64 * 8.0).round() as usize;
+    let mut bar = "█".repeat(eighths / 8);
+    let len = match eighths % 8 {
+        0 => eighths / 8,
+        r => {
+            bar.push(PARTIAL[r - 1]);
+            eighths / 8 + 1
+        }
+    };
+    bar + &" ".repeat(width - len)
+}
+
+/// Background of a terminal, light or dark
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Background {
+    Light,
+    Dark,
+}
+
+/// Guess the background of a terminal from a `COLORFGBG` value, like `"15;0"` or `"15;default;0"`,
+/// whose last field is the background color
+pub fn parse_colorfgbg(value: &str) -> Option<Background> {
+    match value.rsplit(';').next()?.trim().parse::<u8>().ok()? {
+        0..=6 | 8 => Some(Background::Dark),
+        7 | 9..=15 => Some(Background::Light),
+        _ => None,
+    }
+}
+
+/// Guess the background of the current terminal from the `COLORFGBG` environment variable
+pub fn terminal_background() -> Option<Background> {
+    env::var("COLORFGBG").ok().and_then(|v| parse_colorfgbg(&v))
+}
+
+/// Replace foreground `color` by a similar one readable on `background`
+pub fn readable_color(color: Color, background: Background) -> Color {
+    match (background, color) {
+        (Background::Dark, color::BLACK) => color::WHITE,
+        (Background::Dark, color::BLUE) => color::BRIGHT_BLUE,
+        (Background::Light, color::WHITE) | (Background::Light, color::BRIGHT_WHITE) => color::BLACK,
+        (Background::Light, color::BRIGHT_YELLOW) => color::YELLOW,
+        (Background::Light, color::BRIGHT_CYAN) => color::CYAN,
+        (_, c) => c,
+    }
+}
+
+/// Enable the processing of ANSI escape sequences by the Windows console attached to
+/// standard output, which older consoles print raw otherwise. Does nothing on other platforms
+#[cfg(windows)]
+pub fn enable_windows_ansi() -> io::Result<()> {
+    use std::os::raw::c_void;
+
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> *mut c_void;
+        fn GetConsoleMode(console: *mut c_void, mode: *mut u32) -> i32;
+        fn SetConsoleMode(console: *mut c_void, mode: u32) -> i32;
+    }
+
+    // SAFETY: the handle is checked before use, and `mode` outlives the calls
+    unsafe {
+        let handle = GetStdHandle(STD_OUTPUT_HANDLE);
+        if handle.is_null() || handle as isize == -1 {
+            return Err(io::Error::last_os_error());
+        }
+        let mut mode = 0;
+        if GetConsoleMode(handle, &mut mode) == 0 {
+            return Err(io::Error::last_os_error());
+        }
+        if mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING == 0
+            && SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) == 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+/// Enable the processing of ANSI escape sequences by the Windows console attached to
+/// standard output, which older consoles print raw otherwise. Does nothing on other platforms
+#[cfg(not(windows))]
+pub fn enable_windows_ansi() -> io::Result<()> {
+    Ok(())
+}
+
+/// Call `enable_windows_ansi` the first time colors are printed to standard output.
+/// Failures are ignored, a console without ANSI support still being usable through `term`
+pub fn enable_windows_ansi_once() {
+    static ENABLE: Once = Once::new();
+    ENABLE.call_once(|| {
+        let _ = enable_windows_ansi();
+    });
+}
+
+/// Format `value` in compact notation, like `1.2k` or `3.4M`, with `significant` significant digits.
+/// Integer digits are never dropped, so `123456` is `123k` even with 2 significant digits
+pub fn format_compact(value: f64, significant: usize) -> String {
+    const SUFFIXES: [&str; 7] = ["", "k", "M", "G", "T", "P", "E"];
+    let mut scaled = value.abs();
+    let mut unit = 0;
+    while scaled >= 1000.0 && unit + 1 < SUFFIXES.len() {
+        scaled /= 1000.0;
+        unit += 1;
+    }
+    let mut text;
+    loop {
+        let digits = (scaled.max(1.0).log10().floor() as usize) + 1;
+        text = format!("{:.*}", significant.saturating_sub(digits), scaled);
+        // Rounding may carry over to the next unit, like 999.96k becoming 1000k
+        match text.parse::<f64>() {
+            Ok(r) if r >= 1000.0 && unit + 1 < SUFFIXES.len() => {
+                scaled = r / 1000.0;
+                unit += 1;
+            }
+            _ => break,
+        }
+    }
+    let sign = if value < 0.0 { "-" } else { "" };
+    format!("{}{}{}", sign, text, SUFFIXES[unit])
+}
+
+/// Rewrite the plain decimal number `text`, like `-1234.5`, with `thousands` separating groups
+/// of 3 digits and `decimal` as decimal point. Return `None` if `text` is not a plain decimal number
+pub fn localize_number(text: &str, thousands: Option<char>, decimal: char) -> Option<String> {
+    let text = text.trim();
+    let (sign, digits) = if text.starts_with('-') {
+        ("-", &text[1..])
+    } else if text.starts_with('+') {
+        ("", &text[1..])
+    } else {
+        ("", text)
+    };
+    let (int, frac) = match digits.find('.') {
+        Some(i) => (&digits[..i], Some(&digits[i + 1..])),
+        None => (digits, None),
+    };
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+    if int.is_empty() || !is_digits(int) || frac.iter().any(|f| f.is_empty() || !is_digits(f)) {
+        return None;
+    }
+    let mut out = sign.to_string();
+    for (i, c) in int.chars().enumerate() {
+        if i > 0 && (int.len() - i) % 3 == 0 {
+            out.extend(thousands);
+        }
+        out.push(c);
+    }
+    if let Some(f) = frac {
+        out.push(decimal);
+        out.push_str(f);
+    }
+    Some(out)
+}
+
+/// Split `text` into a leading decimal number and the rest, skipping whitespace around both
+fn split_number(text: &str) -> Option<(f64, &str)> {
+    let text = text.trim_start();
+    let end = text.find(|c: char| !(c.is_ascii_digit() || c == '.')).unwrap_or(text.len());
+    let value = text[..end].parse::<f64>().ok()?;
+    Some((value, text[end..].trim_start()))
+}
+
+/// Parse a human readable size like `1.5 GB`, `200MiB` or `12` into a number of bytes.
+/// Units are case insensitive, `k`, `M`, `G`... are powers of 1000 and `Ki`, `Mi`, `Gi`...
+/// powers of 1024, optionally followed by `B`
+pub fn parse_bytes(text: &str) -> Option<f64> {
+    let (value, unit) = split_number(text)?;
+    let unit = unit.trim_end().to_ascii_lowercase();
+    let unit = if unit.ends_with('b') { &unit[..unit.len() - 1] } else { &unit[..] };
+    let (prefix, base) = if unit.len() > 1 && unit.ends_with('i') {
+        (&unit[..unit.len() - 1], 1024f64)
+    } else {
+        (unit, 1000f64)
+    };
+    let exp = match prefix {
+        "" => 0,
+        "k" => 1,
+        "m" => 2,
+        "g" => 3,
+        "t" => 4,
+        "p" => 5,
+        "e" => 6,
+        _ => return None,
+    };
+    Some(value * base.powi(exp))
+}
+
+/// Parse a human readable duration like `2h30m`, `45s` or `1.5 d` into a number of seconds.
+/// Units are `ms`, `s`, `m` or `min`, `h`, `d` and `w`. A number without unit is in seconds
+pub fn parse_duration(text: &str) -> Option<f64> {
+    let mut rest = text.trim();
+    if rest.is_empty() {
+        return None;
+    }
+    let mut total = 0.0;
+    while !rest.is_empty() {
+        let (value, tail) = split_number(rest)?;
+        let end = tail.find(|c: char| !c.is_ascii_alphabetic()).unwrap_or(tail.len());
+        let scale = match &tail[..end] {
+            "ms" => 0.001,
+            "" | "s" => 1.0,
+            "m" | "min" => 60.0,
+            "h" => 3600.0,
+            "d" => 86400.0,
+            "w" => 604_800.0,
+            _ => return None,
+        };
+        total += value * scale;
+        rest = tail[end..].trim_start();
+    }
+    Some(total)
+}
+
+/// Parse a date written `YYYY-MM-DD` into the number `YYYYMMDD`, which sorts like dates.
+/// Return `None` if `text` is not a valid date
+pub fn parse_date(text: &str) -> Option<f64> {
+    let mut parts = text.trim().split('-');
+    let mut field = |len: usize| parts.next()
+        .filter(|p| p.len() == len && p.chars().all(|c| c.is_ascii_digit()))
+        .and_then(|p| p.parse::<u32>().ok());
+    let (year, month, day) = (field(4)?, field(2)?, field(2)?);
+    if parts.next().is_some() {
+        return None;
+    }
+    let leap = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if day == 0 || day > days {
+        return None;
+    }
+    Some(f64::from(year * 10000 + month * 100 + day))
+}
+
 /// Return the display width of a unicode string.
 /// This functions takes ANSI-escaped color codes into account.
 pub fn display_width(text: &str) -> usize {
@@ -86,16 +605,21 @@ pub fn display_width(text: &str) -> usize {
     for c in text.chars() {
         state = match (state, c) {
             (0, '\u{1b}') => 1,
-            (1, '[') => 2,
+            (1, '[') => {
+                // Depending on the version of unicode-width, the escape character
+                // may or may not have been counted by UnicodeWidthStr::width
+                hidden += UnicodeWidthStr::width("\u{1b}");
+                2
+            }
             (1, _) => 0,
             (2, 'm') => 3,
             _ => state,
         };
 
-        // We don't count escape characters as hidden as
-        // UnicodeWidthStr::width already considers them.
         if state > 1 {
-            hidden += 1;
+            // Characters of escape sequences may not be 1 column wide, like in malformed sequences
+            let mut buf = [0; 4];
+            hidden += UnicodeWidthStr::width(&*c.encode_utf8(&mut buf));
         }
 
         if state == 3 {
@@ -103,7 +627,50 @@ pub fn display_width(text: &str) -> usize {
         }
     }
 
-    width - hidden
+    width.saturating_sub(hidden)
+}
+
+/// Split `text` into lines no wider than `width` columns.
+/// Explicit newlines are always honored as hard breaks, then each resulting line is wrapped
+/// on spaces. Words wider than `width` are broken on character boundaries.
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    for line in text.lines() {
+        if display_width(line) <= width {
+            lines.push(line.to_string());
+            continue;
+        }
+        let mut current = String::new();
+        let mut current_width = 0;
+        let mut started = false;
+        for word in line.split(' ') {
+            let word_width = display_width(word);
+            if started && current_width + 1 + word_width <= width {
+                current.push(' ');
+                current.push_str(word);
+                current_width += 1 + word_width;
+                continue;
+            }
+            if started {
+                lines.push(current);
+                current = String::new();
+                current_width = 0;
+            }
+            started = true;
+            for c in word.chars() {
+                let w = UnicodeWidthChar::width(c).unwrap_or(0);
+                if current_width > 0 && current_width + w > width {
+                    lines.push(current);
+                    current = String::new();
+                    current_width = 0;
+                }
+                current.push(c);
+                current_width += w;
+            }
+        }
+        lines.push(current);
+    }
+    lines
 }
 
 /// Wrapper struct which will emit the HTML-escaped version of the contained
@@ -149,6 +716,14 @@ mod tests {
     use crate::format::Alignment;
     use std::io::Write;
 
+    #[cfg(not(windows))]
+    #[test]
+    fn enable_windows_ansi_noop() {
+        assert!(enable_windows_ansi().is_ok());
+        enable_windows_ansi_once();
+        enable_windows_ansi_once();
+    }
+
     #[test]
     fn string_writer() {
         let mut out = StringWriter::new();
@@ -159,6 +734,99 @@ mod tests {
         assert_eq!(out.as_string(), "foo bar");
     }
 
+    #[test]
+    fn counting_writer() {
+        let mut out = CountingWriter::new(StringWriter::new());
+        out.write_all(b"foo").unwrap();
+        out.write_all("é".as_bytes()).unwrap();
+        out.flush().unwrap();
+        assert_eq!(out.bytes_written(), 5);
+        assert_eq!(out.get_ref().as_string(), "fooé");
+        out.get_mut().write_all(b"!").unwrap();
+        assert_eq!(out.bytes_written(), 5);
+        assert_eq!(out.into_inner().as_string(), "fooé!");
+    }
+
+    #[test]
+    fn colorfgbg() {
+        assert_eq!(parse_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(parse_colorfgbg("0;default;15"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("0;7"), Some(Background::Light));
+        assert_eq!(parse_colorfgbg("0;42"), None);
+        assert_eq!(parse_colorfgbg("default"), None);
+        assert_eq!(parse_colorfgbg(""), None);
+    }
+
+    #[test]
+    fn readable_colors() {
+        assert_eq!(readable_color(color::BRIGHT_WHITE, Background::Light), color::BLACK);
+        assert_eq!(readable_color(color::BRIGHT_WHITE, Background::Dark), color::BRIGHT_WHITE);
+        assert_eq!(readable_color(color::BLACK, Background::Dark), color::WHITE);
+        assert_eq!(readable_color(color::RED, Background::Dark), color::RED);
+    }
+
+    #[test]
+    fn compact_numbers() {
+        assert_eq!(format_compact(1234.0, 2), "1.2k");
+        assert_eq!(format_compact(3_400_000.0, 2), "3.4M");
+        assert_eq!(format_compact(5_649_000_000.0, 3), "5.65G");
+        assert_eq!(format_compact(-1500.0, 2), "-1.5k");
+        assert_eq!(format_compact(123_456.0, 2), "123k");
+        assert_eq!(format_compact(999_960.0, 3), "1.00M");
+        assert_eq!(format_compact(12.0, 3), "12.0");
+        assert_eq!(format_compact(2e21, 1), "2000E");
+    }
+
+    #[test]
+    fn localized_numbers() {
+        assert_eq!(localize_number("1234567.891", Some('.'), ','), Some("1.234.567,891".to_string()));
+        assert_eq!(localize_number(" -1234 ", Some(','), '.'), Some("-1,234".to_string()));
+        assert_eq!(localize_number("123.5", Some(','), ','), Some("123,5".to_string()));
+        assert_eq!(localize_number("1000", None, ','), Some("1000".to_string()));
+        assert_eq!(localize_number("1e5", Some(','), '.'), None);
+        assert_eq!(localize_number("1.", Some(','), '.'), None);
+        assert_eq!(localize_number("abc", Some(','), '.'), None);
+    }
+
+    #[test]
+    fn human_bytes() {
+        assert_eq!(parse_bytes("1.5 GB"), Some(1.5e9));
+        assert_eq!(parse_bytes("200mb"), Some(2e8));
+        assert_eq!(parse_bytes("2 KiB"), Some(2048.0));
+        assert_eq!(parse_bytes("1Gi"), Some(1_073_741_824.0));
+        assert_eq!(parse_bytes(" 12 "), Some(12.0));
+        assert_eq!(parse_bytes("12 B"), Some(12.0));
+        assert_eq!(parse_bytes("3 XB"), None);
+        assert_eq!(parse_bytes("ib"), None);
+        assert_eq!(parse_bytes("-"), None);
+    }
+
+    #[test]
+    fn human_durations() {
+        assert_eq!(parse_duration("2h30m"), Some(9000.0));
+        assert_eq!(parse_duration("45s"), Some(45.0));
+        assert_eq!(parse_duration("1.5 d"), Some(129_600.0));
+        assert_eq!(parse_duration("1w 2min 500ms"), Some(604_920.5));
+        assert_eq!(parse_duration("90"), Some(90.0));
+        assert_eq!(parse_duration("5 years"), None);
+        assert_eq!(parse_duration("h"), None);
+        assert_eq!(parse_duration(""), None);
+    }
+
+    #[test]
+    fn dates() {
+        assert_eq!(parse_date("2024-02-29"), Some(20_240_229.0));
+        assert_eq!(parse_date(" 1999-12-31 "), Some(19_991_231.0));
+        assert_eq!(parse_date("2023-02-29"), None);
+        assert_eq!(parse_date("1900-02-29"), None);
+        assert_eq!(parse_date("2000-02-29"), Some(20_000_229.0));
+        assert_eq!(parse_date("2024-13-01"), None);
+        assert_eq!(parse_date("2024-04-31"), None);
+        assert_eq!(parse_date("2024-1-01"), None);
+        assert_eq!(parse_date("2024-01-01-01"), None);
+        assert_eq!(parse_date("+024-01-01"), None);
+    }
+
     #[test]
     fn fill_align() {
         let mut out = StringWriter::new();
@@ -197,6 +865,89 @@ mod tests {
         assert_eq!(out.as_string(), "foo");
     }
 
+    #[test]
+    fn ansi_width() {
+        assert_eq!(display_width("\x1b[7mqux\x1b[27m"), 3);
+        assert_eq!(display_width("\x1b[1;31mred\x1b[0m!"), 4);
+        // Malformed sequences must not underflow
+        assert_eq!(display_width("\x1b[\u{301}"), 0);
+        assert_eq!(display_width("\x1b[漢"), 0);
+    }
+
+    #[test]
+    fn anchor() {
+        assert_eq!(anchor_offset("key: value", ":"), 3);
+        assert_eq!(anchor_offset("12 ms", " ms"), 2);
+        assert_eq!(anchor_offset("n/a", " ms"), 3);
+        assert_eq!(anchor_offset("", ":"), 0);
+    }
+
+    #[test]
+    fn truncate_width() {
+        assert_eq!(truncate("foobar", 3), "foo");
+        assert_eq!(truncate("foo", 10), "foo");
+        assert_eq!(truncate("由系统", 3), "由");
+        assert_eq!(truncate("foo", 0), "");
+        assert_eq!(truncate("\u{1b}[31mfoobar\u{1b}[0m", 3), "\u{1b}[31mfoo");
+        assert_eq!(truncate("foo\u{1b}[0m", 3), "foo\u{1b}[0m");
+        assert_eq!(truncate_start("\u{1b}[31mfoobar\u{1b}[0m", 3), "bar\u{1b}[0m");
+        assert_eq!(escape_sequences("\u{1b}[31mfoo\u{1b}[1;4mbar\u{1b}[0m"), "\u{1b}[31m\u{1b}[1;4m\u{1b}[0m");
+    }
+
+    #[test]
+    fn clip_text() {
+        assert_eq!(truncate_start("foobar", 3), "bar");
+        assert_eq!(truncate_start("由系统", 3), "统");
+        assert_eq!(clip("foo", 5, Alignment::LEFT), "foo");
+        assert_eq!(clip("foobar", 4, Alignment::LEFT), "foo›");
+        assert_eq!(clip("foobar", 4, Alignment::RIGHT), "‹bar");
+        assert_eq!(clip("foobarbaz", 5, Alignment::CENTER), "‹bar›");
+        assert_eq!(clip("foobar", 1, Alignment::CENTER), "›");
+        assert_eq!(clip("foobar", 0, Alignment::RIGHT), "");
+    }
+
+    #[test]
+    fn fit() {
+        assert_eq!(fit_proportional(&[4, 2], 10), vec![4, 2]);
+        assert_eq!(fit_proportional(&[30, 10], 20), vec![15, 5]);
+        assert_eq!(fit_proportional(&[10, 10, 10], 10), vec![4, 3, 3]);
+        assert_eq!(fit_proportional(&[100, 1, 0], 10), vec![9, 1, 0]);
+        assert_eq!(fit_balanced(&[vec!["abcd"], vec!["ab"]], 10), vec![4, 2]);
+        assert_eq!(fit_balanced(&[vec!["aaaa aaaa", "bbbb bbbb"], vec!["cc"]], 6), vec![4, 2]);
+        assert_eq!(fit_balanced(&[vec!["aaa"], vec!["bbb"]], 1), vec![1, 1]);
+        assert_eq!(fit_balanced(&[vec![], vec!["abc"]], 2), vec![0, 2]);
+    }
+
+    #[test]
+    fn bar() {
+        assert_eq!(render_bar(0.5, 4), "██  ");
+        assert_eq!(render_bar(1.0, 3), "███");
+        assert_eq!(render_bar(0.0, 3), "   ");
+        assert_eq!(render_bar(2.0, 2), "██");
+        assert_eq!(render_bar(-1.0, 2), "  ");
+        assert_eq!(render_bar(f64::NAN, 2), "  ");
+        assert_eq!(render_bar(0.3, 5), "█▌   ");
+    }
+
+    #[test]
+    fn interner() {
+        let mut interner = Interner::default();
+        let a = interner.intern("OK");
+        let b = interner.intern("OK");
+        assert!(Arc::ptr_eq(&a, &b));
+        assert!(!Arc::ptr_eq(&a, &interner.intern("FAIL")));
+    }
+
+    #[test]
+    fn wrap() {
+        assert_eq!(wrap_text("foo bar baz", 7), vec!["foo bar", "baz"]);
+        assert_eq!(wrap_text("foo bar baz", 20), vec!["foo bar baz"]);
+        assert_eq!(wrap_text("foobarbaz", 4), vec!["foob", "arba", "z"]);
+        assert_eq!(wrap_text("由系统自动", 4), vec!["由系", "统自", "动"]);
+        assert_eq!(wrap_text("ab\n\ncd ef", 2), vec!["ab", "", "cd", "ef"]);
+        assert!(wrap_text("", 3).is_empty());
+    }
+
     #[test]
     fn utf8_error() {
         let mut out = StringWriter::new();